@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use color_eyre::eyre::Context;
+use color_eyre::Result;
+use common::input::workspace_root;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub fn path() -> PathBuf {
+    workspace_root().join(".aoc/cache.json")
+}
+
+/// Identifies what an answer was computed from: the input and the version of the solver
+pub fn hash(input: &str, version: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(version);
+    hasher.update([0]);
+    hasher.update(input);
+
+    format!("{:x}", hasher.finalize())
+}
+
+/// Identifies an input alone, so runs on the example and on the real input can be told apart
+pub fn input_hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub hash: String,
+    pub answer: String,
+    pub duration: Duration,
+}
+
+/// Answers by solver id, at most one per solver
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Cache {
+    /// An empty cache if there's no file yet
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .wrap_err_with(|| format!("parsing {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).wrap_err_with(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("writing {}", path.display()))
+    }
+
+    /// An entry computed from something else (input or solver changed) is stale and gets evicted
+    pub fn get(&mut self, id: &str, hash: &str) -> Option<&Entry> {
+        if self.entries.get(id)?.hash != hash {
+            self.entries.remove(id);
+            return None;
+        }
+
+        self.entries.get(id)
+    }
+
+    /// Evicts the entries of solvers that don't exist anymore
+    pub fn prune(&mut self, solvers: &[String]) {
+        self.entries.retain(|id, _| solvers.contains(id));
+    }
+
+    pub fn insert(&mut self, id: String, entry: Entry) {
+        self.entries.insert(id, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::temp::TempDir;
+
+    use super::*;
+
+    fn entry(hash: &str) -> Entry {
+        Entry {
+            hash: hash.into(),
+            answer: "42".into(),
+            duration: Duration::from_millis(3),
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("A Y\n", "0.1.0"), hash("A Y\n", "0.1.0"));
+        assert_ne!(hash("A Y\n", "0.1.0"), hash("A Y\n", "0.1.1"));
+        assert_ne!(hash("A Y\n", "0.1.0"), hash("A X\n", "0.1.0"));
+    }
+
+    #[test]
+    fn test_stale_entries_are_evicted() {
+        let mut cache = Cache::default();
+        cache.insert("day2.p1.default".into(), entry("abc"));
+
+        assert_eq!(cache.get("day2.p1.default", "abc"), Some(&entry("abc")));
+        assert_eq!(cache.get("day2.p2.default", "abc"), None);
+
+        assert_eq!(cache.get("day2.p1.default", "def"), None);
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn test_removed_solvers_are_evicted() {
+        let mut cache = Cache::default();
+        cache.insert("day2.p1.default".into(), entry("abc"));
+        cache.insert("day2.p1.gone".into(), entry("abc"));
+
+        cache.prune(&["day2.p1.default".into(), "day2.p2.default".into()]);
+
+        assert_eq!(
+            cache.entries.keys().collect::<Vec<_>>(),
+            vec!["day2.p1.default"]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = TempDir::new("cache").unwrap();
+        let path = dir.join("cache.json");
+
+        assert!(Cache::load(&path).unwrap().entries.is_empty());
+
+        let mut cache = Cache::default();
+        cache.insert("day2.p1.default".into(), entry("abc"));
+        cache.save(&path).unwrap();
+
+        let mut cache = Cache::load(&path).unwrap();
+        assert_eq!(cache.get("day2.p1.default", "abc"), Some(&entry("abc")));
+    }
+}
@@ -0,0 +1,423 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+/// Anything below this is reported as "not this day"
+const MIN_CONFIDENCE: f64 = 0.5;
+
+/// Confidence (0 to 1) that some lines match a day's input format
+type Grammar = fn(&[&str]) -> f64;
+
+/// Every day's grammar, checked against the lines of the input (without surrounding blank lines)
+const GRAMMARS: [(u8, Grammar); 8] = [
+    (1, day1),
+    (2, day2),
+    (3, day3),
+    (4, day4),
+    (5, day5),
+    (6, day6),
+    (7, day7),
+    (8, day8),
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Report {
+    /// Days the input could belong to, most likely first
+    pub candidates: Vec<Candidate>,
+    pub warnings: Vec<Warning>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Candidate {
+    pub day: u8,
+    /// Between 0 and 1
+    pub confidence: f64,
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day{} ({:.0}%)", self.day, self.confidence * 100.0)
+    }
+}
+
+/// Formatting problems that don't stop the input from being recognized, but may trip up a solver
+#[derive(Debug, PartialEq, Eq)]
+pub enum Warning {
+    CrLf { lines: usize },
+    MissingTrailingNewline,
+    TrailingWhitespace { first_line: usize, lines: usize },
+    LeadingBlankLines,
+    TrailingBlankLines,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CrLf { lines } => write!(f, "{lines} line(s) end in CRLF"),
+            Self::MissingTrailingNewline => write!(f, "missing trailing newline"),
+            Self::TrailingWhitespace { first_line, lines } => write!(
+                f,
+                "{lines} line(s) have trailing whitespace (first at line {first_line})"
+            ),
+            Self::LeadingBlankLines => write!(f, "blank line(s) at the start of the file"),
+            Self::TrailingBlankLines => write!(f, "blank line(s) at the end of the file"),
+        }
+    }
+}
+
+pub fn classify(input: &str) -> Report {
+    let lines = input.lines().map(str::trim_end).collect_vec();
+
+    // Surrounding blank lines are reported as warnings, not held against any grammar
+    let start = lines
+        .iter()
+        .position(|line| !line.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(start, |i| i + 1);
+    let content = &lines[start..end];
+
+    let candidates = if content.is_empty() {
+        vec![]
+    } else {
+        GRAMMARS
+            .iter()
+            .map(|(day, grammar)| Candidate {
+                day: *day,
+                confidence: grammar(content),
+            })
+            .filter(|candidate| candidate.confidence >= MIN_CONFIDENCE)
+            .sorted_by(|a, b| b.confidence.total_cmp(&a.confidence))
+            .collect()
+    };
+
+    Report {
+        candidates,
+        warnings: lint(input),
+    }
+}
+
+fn lint(input: &str) -> Vec<Warning> {
+    let mut warnings = vec![];
+
+    let crlf = input.matches("\r\n").count();
+    if crlf > 0 {
+        warnings.push(Warning::CrLf { lines: crlf });
+    }
+
+    if !input.is_empty() && !input.ends_with('\n') {
+        warnings.push(Warning::MissingTrailingNewline);
+    }
+
+    let trailing_whitespace = input
+        .lines()
+        .positions(|line| line.ends_with(char::is_whitespace))
+        .collect_vec();
+    if let Some(first) = trailing_whitespace.first() {
+        warnings.push(Warning::TrailingWhitespace {
+            first_line: first + 1,
+            lines: trailing_whitespace.len(),
+        });
+    }
+
+    if input
+        .lines()
+        .next()
+        .is_some_and(|line| line.trim().is_empty())
+    {
+        warnings.push(Warning::LeadingBlankLines);
+    }
+
+    if input.lines().count() > 1 && input.lines().last().is_some_and(|l| l.trim().is_empty()) {
+        warnings.push(Warning::TrailingBlankLines);
+    }
+
+    warnings
+}
+
+/// Share of lines matching `predicate`
+fn ratio(lines: &[&str], predicate: impl Fn(&str) -> bool) -> f64 {
+    lines.iter().filter(|line| predicate(line)).count() as f64 / lines.len() as f64
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Groups of numbers separated by blank lines
+fn day1(lines: &[&str]) -> f64 {
+    let confidence = ratio(lines, |line| line.is_empty() || is_number(line));
+
+    // A single elf is valid, but then it looks like a one-column day8 grid
+    if lines.contains(&"") {
+        confidence
+    } else {
+        confidence / 2.0
+    }
+}
+
+/// `A Y`
+fn day2(lines: &[&str]) -> f64 {
+    ratio(lines, |line| {
+        matches!(line.as_bytes(), [b'A'..=b'C', b' ', b'X'..=b'Z'])
+    })
+}
+
+/// Rucksacks of letters, split in two compartments and grouped by 3
+fn day3(lines: &[&str]) -> f64 {
+    let confidence = ratio(lines, |line| {
+        !line.is_empty()
+            && line.len().is_multiple_of(2)
+            && line.bytes().all(|b| b.is_ascii_alphabetic())
+    });
+
+    if lines.len().is_multiple_of(3) {
+        confidence
+    } else {
+        confidence * 0.9
+    }
+}
+
+/// `2-4,6-8`
+fn day4(lines: &[&str]) -> f64 {
+    let is_range = |range: &str| {
+        range
+            .split_once('-')
+            .is_some_and(|(start, end)| is_number(start) && is_number(end))
+    };
+
+    ratio(lines, |line| {
+        line.split_once(',')
+            .is_some_and(|(a, b)| is_range(a) && is_range(b))
+    })
+}
+
+/// Crate diagram, blank line, `move 1 from 2 to 1`
+fn day5(lines: &[&str]) -> f64 {
+    let Some(separator) = lines.iter().position(|line| line.is_empty()) else {
+        return 0.0;
+    };
+
+    let (stacks, moves) = (&lines[..separator], &lines[separator + 1..]);
+    let Some((labels, crates)) = stacks.split_last() else {
+        return 0.0;
+    };
+
+    let is_labels = labels.bytes().any(|b| b.is_ascii_digit())
+        && labels.bytes().all(|b| b == b' ' || b.is_ascii_digit());
+    if !is_labels {
+        return 0.0;
+    }
+
+    let is_crate_row = |line: &str| {
+        !line.is_empty()
+            && line.as_bytes().chunks(4).all(|chunk| {
+                matches!(
+                    chunk,
+                    [b'[', b'A'..=b'Z', b']']
+                        | [b'[', b'A'..=b'Z', b']', b' ']
+                        | [b' ', b' ', b' ']
+                        | [b' ', b' ', b' ', b' ']
+                )
+            })
+    };
+
+    let is_move = |line: &str| {
+        matches!(
+            line.split(' ').collect_vec().as_slice(),
+            ["move", qty, "from", src, "to", dst] if is_number(qty) && is_number(src) && is_number(dst)
+        )
+    };
+
+    // The labels line matched, the separator doesn't count
+    let matching = 1
+        + crates.iter().filter(|line| is_crate_row(line)).count()
+        + moves.iter().filter(|line| is_move(line)).count();
+
+    matching as f64 / (lines.len() - 1) as f64
+}
+
+/// A single line datastream
+fn day6(lines: &[&str]) -> f64 {
+    match lines {
+        [line] if line.len() >= 14 && line.bytes().all(|b| b.is_ascii_lowercase()) => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Terminal output
+fn day7(lines: &[&str]) -> f64 {
+    let confidence = ratio(lines, |line| {
+        if line == "$ ls" {
+            true
+        } else if let Some(path) = line.strip_prefix("$ cd ") {
+            !path.is_empty()
+        } else if let Some(name) = line.strip_prefix("dir ") {
+            !name.is_empty()
+        } else {
+            line.split_once(' ')
+                .is_some_and(|(size, name)| is_number(size) && !name.is_empty())
+        }
+    });
+
+    if lines[0] == "$ cd /" {
+        confidence
+    } else {
+        confidence * 0.9
+    }
+}
+
+/// Square grid of digits
+fn day8(lines: &[&str]) -> f64 {
+    let width = lines[0].len();
+    let confidence = ratio(lines, |line| line.len() == width && is_number(line));
+
+    if lines.len() == width {
+        confidence
+    } else {
+        confidence * 0.8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(report: &Report) -> Vec<u8> {
+        report
+            .candidates
+            .iter()
+            .map(|candidate| candidate.day)
+            .collect()
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = [
+            (
+                1,
+                indoc::indoc! {"
+                    1000
+                    2000
+                    3000
+
+                    4000
+
+                    5000
+                    6000
+                "},
+            ),
+            (
+                2,
+                indoc::indoc! {"
+                    A Y
+                    B X
+                    C Z
+                "},
+            ),
+            (
+                3,
+                indoc::indoc! {"
+                    vJrwpWtwJgWrhcsFMMfFFhFp
+                    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+                    PmmdzqPrVvPwwTWBwg
+                "},
+            ),
+            (
+                4,
+                indoc::indoc! {"
+                    2-4,6-8
+                    2-3,4-5
+                "},
+            ),
+            (
+                5,
+                indoc::indoc! {"
+                        [D]
+                    [N] [C]
+                    [Z] [M] [P]
+                     1   2   3
+
+                    move 1 from 2 to 1
+                    move 3 from 1 to 3
+                "},
+            ),
+            (6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"),
+            (
+                7,
+                indoc::indoc! {"
+                    $ cd /
+                    $ ls
+                    dir a
+                    14848514 b.txt
+                    $ cd a
+                "},
+            ),
+            (
+                8,
+                indoc::indoc! {"
+                    30373
+                    25512
+                    65332
+                    33549
+                    35390
+                "},
+            ),
+        ];
+
+        for (day, input) in examples {
+            let report = classify(input);
+
+            assert_eq!(days(&report)[0], day, "{report:?}");
+            assert_eq!(report.candidates[0].confidence, 1.0, "{report:?}");
+        }
+    }
+
+    #[test]
+    fn test_ambiguous_input() {
+        // Could be a single elf or a (non-square) grid
+        let report = classify("123\n456\n");
+
+        assert_eq!(days(&report), vec![8, 1]);
+    }
+
+    #[test]
+    fn test_unknown_input() {
+        assert!(classify("hello world\n").candidates.is_empty());
+        assert!(classify("").candidates.is_empty());
+    }
+
+    #[test]
+    fn test_bad_line_lowers_confidence() {
+        let report = classify("A Y\nB X\nC Z\nD W\n");
+
+        assert_eq!(days(&report), vec![2]);
+        assert_eq!(report.candidates[0].confidence, 0.75);
+    }
+
+    #[test]
+    fn test_warnings() {
+        let report = classify("\r\nA Y\r\nB X \r\nC Z");
+
+        assert_eq!(days(&report), vec![2]);
+        assert_eq!(
+            report.warnings,
+            vec![
+                Warning::CrLf { lines: 3 },
+                Warning::MissingTrailingNewline,
+                Warning::TrailingWhitespace {
+                    first_line: 3,
+                    lines: 1
+                },
+                Warning::LeadingBlankLines,
+            ]
+        );
+
+        let report = classify("A Y\n\n\n");
+
+        assert_eq!(report.warnings, vec![Warning::TrailingBlankLines]);
+        assert!(classify("A Y\n").warnings.is_empty());
+    }
+}
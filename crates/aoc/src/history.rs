@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use color_eyre::eyre::Context;
+use color_eyre::Result;
+use common::input::workspace_root;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::run::Run;
+
+pub fn path() -> PathBuf {
+    workspace_root().join(".aoc/history.jsonl")
+}
+
+/// One solver run, as a line of the history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    /// Seconds since the epoch
+    pub timestamp: u64,
+    /// `None` outside of a git checkout
+    pub commit: Option<String>,
+    pub day: u8,
+    pub part: u8,
+    pub name: String,
+    pub answer: String,
+    pub duration: Duration,
+    /// Replayed from the cache, so the duration is from an earlier run
+    pub cached: bool,
+    /// Hash of the input, `None` for records written before it was kept
+    #[serde(default)]
+    pub input: Option<String>,
+}
+
+impl Record {
+    /// `None` for a run that failed, which has nothing to record
+    pub fn new(run: &Run, timestamp: u64, commit: Option<String>) -> Option<Self> {
+        Some(Self {
+            timestamp,
+            commit,
+            day: run.solver.day,
+            part: run.solver.part,
+            name: run.solver.name.to_string(),
+            answer: run.answer.clone().ok()?,
+            duration: run.duration,
+            cached: run.cached,
+            input: run.input.clone(),
+        })
+    }
+
+    pub fn id(&self) -> String {
+        format!("day{}.p{}.{}", self.day, self.part, self.name)
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// Short hash of `HEAD`, suffixed with `-dirty` when there are uncommitted changes
+pub fn current_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(workspace_root())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+
+    Some(if dirty { commit + "-dirty" } else { commit })
+}
+
+pub fn append(path: &Path, records: &[Record]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .wrap_err_with(|| format!("opening {}", path.display()))?;
+
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+
+    Ok(())
+}
+
+/// Oldest first, empty if nothing was recorded yet
+pub fn load(path: &Path) -> Result<Vec<Record>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err).wrap_err_with(|| format!("reading {}", path.display())),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .wrap_err_with(|| format!("{}:{} is not a record", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// How one solver evolved over its runs
+#[derive(Debug, PartialEq, Eq)]
+pub struct Trend {
+    pub id: String,
+    pub day: u8,
+    pub runs: usize,
+    pub first: Duration,
+    pub last: Duration,
+    pub best: Duration,
+    pub best_commit: Option<String>,
+    pub changes: Vec<Change>,
+}
+
+/// The answer of a solver differs from its previous run on the same input
+#[derive(Debug, PartialEq, Eq)]
+pub struct Change {
+    pub from: String,
+    pub to: String,
+    pub commit: Option<String>,
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} run(s), {:?} -> {:?}",
+            self.id, self.runs, self.first, self.last
+        )?;
+
+        // Too fast to be measured the first time, so there's nothing to compare with
+        if !self.first.is_zero() {
+            let delta = (self.last.as_secs_f64() / self.first.as_secs_f64() - 1.0) * 100.0;
+            write!(f, " ({delta:+.0}%)")?;
+        }
+
+        write!(f, ", best {:?}", self.best)?;
+
+        if let Some(commit) = &self.best_commit {
+            write!(f, " at {commit}")?;
+        }
+
+        for change in &self.changes {
+            let commit = change.commit.as_deref().unwrap_or("?");
+            write!(
+                f,
+                "\n  answer changed at {commit}: {} -> {}",
+                change.from, change.to
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One trend per solver, by day. Cached runs are skipped since they only replay an earlier one
+pub fn trends(records: &[Record]) -> Vec<Trend> {
+    records
+        .iter()
+        .filter(|record| !record.cached)
+        .into_group_map_by(|record| (record.day, record.part, record.name.clone()))
+        .into_iter()
+        .sorted_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, runs)| {
+            let best = runs.iter().min_by_key(|record| record.duration).unwrap();
+
+            // Answers only compare on the same input, runs on the example and on the real input
+            // can alternate
+            let mut previous = HashMap::new();
+            let changes = runs
+                .iter()
+                .filter_map(|&current| {
+                    let previous = previous.insert(&current.input, current)?;
+
+                    (previous.answer != current.answer).then(|| Change {
+                        from: previous.answer.clone(),
+                        to: current.answer.clone(),
+                        commit: current.commit.clone(),
+                    })
+                })
+                .collect();
+
+            Trend {
+                id: runs[0].id(),
+                day: runs[0].day,
+                runs: runs.len(),
+                first: runs[0].duration,
+                last: runs[runs.len() - 1].duration,
+                best: best.duration,
+                best_commit: best.commit.clone(),
+                changes,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::temp::TempDir;
+
+    use super::*;
+    use crate::solvers::{Solve, Solver};
+
+    fn record(commit: &str, name: &str, answer: &str, micros: u64) -> Record {
+        Record {
+            timestamp: 0,
+            commit: Some(commit.into()),
+            day: 3,
+            part: 1,
+            name: name.into(),
+            answer: answer.into(),
+            duration: Duration::from_micros(micros),
+            cached: false,
+            input: Some("real".into()),
+        }
+    }
+
+    #[test]
+    fn test_trends() {
+        let records = vec![
+            record("aaa", "slow", "7817", 500),
+            record("aaa", "fast", "7817", 30),
+            Record {
+                cached: true,
+                ..record("bbb", "slow", "7817", 500)
+            },
+            record("ccc", "slow", "7817", 400),
+            record("ccc", "fast", "7818", 20),
+            record("ddd", "fast", "7817", 25),
+        ];
+
+        let trends = trends(&records);
+
+        assert_eq!(
+            trends,
+            vec![
+                Trend {
+                    id: "day3.p1.fast".into(),
+                    day: 3,
+                    runs: 3,
+                    first: Duration::from_micros(30),
+                    last: Duration::from_micros(25),
+                    best: Duration::from_micros(20),
+                    best_commit: Some("ccc".into()),
+                    changes: vec![
+                        Change {
+                            from: "7817".into(),
+                            to: "7818".into(),
+                            commit: Some("ccc".into()),
+                        },
+                        Change {
+                            from: "7818".into(),
+                            to: "7817".into(),
+                            commit: Some("ddd".into()),
+                        },
+                    ],
+                },
+                Trend {
+                    id: "day3.p1.slow".into(),
+                    day: 3,
+                    runs: 2,
+                    first: Duration::from_micros(500),
+                    last: Duration::from_micros(400),
+                    best: Duration::from_micros(400),
+                    best_commit: Some("ccc".into()),
+                    changes: vec![],
+                },
+            ]
+        );
+
+        assert_eq!(
+            trends[1].to_string(),
+            "day3.p1.slow: 2 run(s), 500µs -> 400µs (-20%), best 400µs at ccc"
+        );
+    }
+
+    #[test]
+    fn test_changes_on_the_same_input_only() {
+        let example = |commit, answer| Record {
+            input: Some("example".into()),
+            ..record(commit, "slow", answer, 0)
+        };
+
+        let records = vec![
+            example("aaa", "157"),
+            record("aaa", "slow", "7817", 500),
+            example("bbb", "157"),
+            record("bbb", "slow", "7817", 400),
+            example("ccc", "158"),
+        ];
+
+        let trends = trends(&records);
+
+        assert_eq!(
+            trends[0].changes,
+            vec![Change {
+                from: "157".into(),
+                to: "158".into(),
+                commit: Some("ccc".into()),
+            }]
+        );
+        assert_eq!(
+            trends[0].to_string(),
+            "day3.p1.slow: 5 run(s), 0ns -> 0ns, best 0ns at aaa\n  answer changed at ccc: 157 -> 158"
+        );
+    }
+
+    #[test]
+    fn test_failed_runs_are_not_recorded() {
+        let solver = Solver {
+            day: 2,
+            part: 1,
+            name: "default",
+            version: "0.1.0",
+            example: "",
+            origin: "builtin",
+            solve: Solve::Builtin(|_| Ok("15".into())),
+        };
+        let run = |answer| Run {
+            solver,
+            answer,
+            duration: Duration::ZERO,
+            cached: false,
+            input: Some("abc".into()),
+        };
+
+        assert_eq!(Record::new(&run(Err("panicked".into())), 0, None), None);
+        assert_eq!(
+            Record::new(&run(Ok("15".into())), 0, None).map(|record| record.answer),
+            Some("15".into())
+        );
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let dir = TempDir::new("history").unwrap();
+        let path = dir.join("history.jsonl");
+
+        assert!(load(&path).unwrap().is_empty());
+
+        append(&path, &[record("aaa", "slow", "1", 1)]).unwrap();
+        append(&path, &[record("bbb", "slow", "2", 2)]).unwrap();
+
+        assert_eq!(
+            load(&path).unwrap(),
+            vec![record("aaa", "slow", "1", 1), record("bbb", "slow", "2", 2)]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use itertools::Itertools;
+
+use self::run::CacheMode;
+
+mod cache;
+mod detect;
+mod history;
+mod plugins;
+mod run;
+mod secrets;
+mod solvers;
+
+/// Tooling shared by all the days
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Where to load solver plugins from [default: .aoc/plugins]
+    #[arg(long, global = true)]
+    plugins: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Guess which day(s) an input file belongs to
+    Detect {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Solve days (all of them by default) and remember the answers
+    Run {
+        days: Vec<u8>,
+        #[arg(short, long)]
+        part: Option<u8>,
+        /// Reuse answers computed from the same input and solver version
+        #[arg(long, conflicts_with = "force")]
+        cached: bool,
+        /// Don't read nor write the cache
+        #[arg(long)]
+        force: bool,
+    },
+    /// List the solvers, builtin and from plugins
+    Solvers { days: Vec<u8> },
+    /// Run every implementation of a day and compare answers and timings
+    Compare { day: u8 },
+    /// Show how answers and timings evolved over the recorded runs
+    History { days: Vec<u8> },
+    /// Generate the key used to encrypt the inputs
+    Keygen,
+    /// Encrypt inputs so they can be committed (all days' `input.txt` by default)
+    Encrypt { paths: Vec<PathBuf> },
+    /// Decrypt committed inputs (all days' `input.txt.enc` by default)
+    Decrypt { paths: Vec<PathBuf> },
+}
+
+pub fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let cli = Cli::parse();
+    let plugin_dir = cli.plugins.unwrap_or_else(plugins::default_dir);
+
+    match cli.command {
+        Command::Detect { paths } => {
+            for path in paths {
+                let input = std::fs::read_to_string(&path)?;
+                let report = detect::classify(&input);
+
+                println!("{}", path.display());
+
+                if report.candidates.is_empty() {
+                    println!("  no matching day");
+                }
+
+                for candidate in &report.candidates {
+                    println!("  {candidate}");
+                }
+
+                for warning in &report.warnings {
+                    println!("  warning: {warning}");
+                }
+            }
+        }
+        Command::Run {
+            days,
+            part,
+            cached,
+            force,
+        } => {
+            let mode = match (cached, force) {
+                (_, true) => CacheMode::Bypass,
+                (true, false) => CacheMode::Cached,
+                (false, false) => CacheMode::Refresh,
+            };
+
+            let solvers = plugins::all(&plugin_dir)?;
+            let runs = run::run(&solvers, &days, part, mode)?;
+
+            let (timestamp, commit) = (history::now(), history::current_commit());
+            let records = runs
+                .iter()
+                .filter_map(|run| history::Record::new(run, timestamp, commit.clone()))
+                .collect_vec();
+            history::append(&history::path(), &records)?;
+
+            for run in &runs {
+                match &run.answer {
+                    Ok(answer) => {
+                        let cached = if run.cached { ", cached" } else { "" };
+                        println!("{}: {answer} ({:?}{cached})", run.solver.id(), run.duration);
+                    }
+                    Err(err) => println!("{}: failed: {err}", run.solver.id()),
+                }
+            }
+
+            let failed = runs.iter().filter(|run| run.answer.is_err()).count();
+            if failed > 0 {
+                return Err(eyre!("{failed} solver(s) failed"));
+            }
+        }
+        Command::Solvers { days } => {
+            for solver in plugins::all(&plugin_dir)? {
+                if days.is_empty() || days.contains(&solver.day) {
+                    println!("{} v{} ({})", solver.id(), solver.version, solver.origin);
+                }
+            }
+        }
+        Command::Compare { day } => {
+            let solvers = plugins::all(&plugin_dir)?;
+
+            for comparison in run::compare(&solvers, day)? {
+                let fastest = comparison.runs[0].duration.as_secs_f64();
+                let verdict = if comparison.agree() {
+                    "answers agree"
+                } else {
+                    "ANSWERS DIFFER"
+                };
+
+                println!("day{day}.p{}: {verdict}", comparison.part);
+
+                for run in &comparison.runs {
+                    let answer = match &run.answer {
+                        Ok(answer) => answer.clone(),
+                        Err(err) => format!("failed: {err}"),
+                    };
+                    println!(
+                        "  {}: {answer} ({:?}, {:.1}x, {})",
+                        run.solver.name,
+                        run.duration,
+                        run.duration.as_secs_f64() / fastest,
+                        run.solver.origin
+                    );
+                }
+            }
+        }
+        Command::History { days } => {
+            let records = history::load(&history::path())?;
+            let trends = history::trends(&records)
+                .into_iter()
+                .filter(|trend| days.is_empty() || days.contains(&trend.day))
+                .collect_vec();
+
+            for trend in &trends {
+                println!("{trend}");
+            }
+
+            let changed = trends
+                .iter()
+                .filter(|trend| !trend.changes.is_empty())
+                .map(|trend| format!("day{}", trend.day))
+                .dedup()
+                .collect_vec();
+
+            if !changed.is_empty() {
+                println!("\nanswers changed between runs: {}", changed.join(", "));
+            }
+        }
+        Command::Keygen => secrets::keygen()?,
+        Command::Encrypt { paths } => secrets::encrypt(paths)?,
+        Command::Decrypt { paths } => secrets::decrypt(paths)?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::ffi::{CStr, OsStr};
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+use common::input::workspace_root;
+use common::plugin::{self, AbiVersionFn, SolversFn};
+use libloading::Library;
+use sha2::{Digest, Sha256};
+
+use crate::solvers::{self, Solve, Solver};
+
+pub fn default_dir() -> PathBuf {
+    workspace_root().join(".aoc/plugins")
+}
+
+/// Solvers of every shared library in `dir`. Libraries stay loaded for the rest of the process
+pub fn load_dir(dir: &Path) -> Result<Vec<Solver>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut paths = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.extension() == Some(OsStr::new(std::env::consts::DLL_EXTENSION)) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut solvers = vec![];
+    for path in paths {
+        solvers.extend(load(&path).wrap_err_with(|| format!("loading {}", path.display()))?);
+    }
+
+    Ok(solvers)
+}
+
+/// Versions are the plugin's followed by a hash of the library, like the builtin days' source
+/// hash, so a rebuilt plugin never replays answers cached from the previous build
+fn load(path: &Path) -> Result<Vec<Solver>> {
+    let library_hash = format!("{:x}", Sha256::digest(std::fs::read(path)?));
+    // Leaked on purpose: solvers keep pointers into the library
+    let library: &'static Library = Box::leak(Box::new(unsafe { Library::new(path)? }));
+    let origin: &'static str = Box::leak(
+        path.file_name()
+            .map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().to_string(),
+            )
+            .into_boxed_str(),
+    );
+
+    let abi_version = unsafe { library.get::<AbiVersionFn>(plugin::ABI_VERSION_SYMBOL)?() };
+    if abi_version != plugin::ABI_VERSION {
+        return Err(eyre!(
+            "built against plugin ABI v{abi_version}, aoc speaks v{}",
+            plugin::ABI_VERSION
+        ));
+    }
+
+    let solvers = unsafe {
+        let mut len = 0;
+        let ptr = library.get::<SolversFn>(plugin::SOLVERS_SYMBOL)?(&mut len);
+
+        std::slice::from_raw_parts(ptr, len)
+    };
+
+    solvers
+        .iter()
+        .map(|solver| {
+            let name = unsafe { CStr::from_ptr(solver.name) }.to_str()?;
+            let version = unsafe { CStr::from_ptr(solver.version) }.to_str()?;
+            let version: &'static str =
+                Box::leak(format!("{version}+{}", &library_hash[..16]).into_boxed_str());
+
+            Ok(Solver {
+                day: solver.day,
+                part: solver.part,
+                name,
+                version,
+                example: solvers::example(solver.day),
+                origin,
+                solve: Solve::Plugin(solver.solve),
+            })
+        })
+        .collect()
+}
+
+/// Builtin solvers followed by the plugins', which can't reuse a builtin id
+pub fn all(dir: &Path) -> Result<Vec<Solver>> {
+    let mut all = solvers::SOLVERS.to_vec();
+
+    for solver in load_dir(dir)? {
+        if let Some(existing) = all.iter().find(|existing| existing.id() == solver.id()) {
+            return Err(eyre!(
+                "{} from {} is already provided by {}",
+                solver.id(),
+                solver.origin,
+                existing.origin
+            ));
+        }
+
+        all.push(solver);
+    }
+
+    all.sort_by_key(|solver| (solver.day, solver.part));
+
+    Ok(all)
+}
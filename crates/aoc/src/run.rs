@@ -0,0 +1,167 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use common::input::day_dir;
+use itertools::Itertools;
+
+use crate::cache::{self, Cache, Entry};
+use crate::solvers::Solver;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Always solve, remember the answers
+    Refresh,
+    /// Reuse answers computed from the same input and solver version
+    Cached,
+    /// Always solve, don't touch the cache
+    Bypass,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub solver: Solver,
+    /// The error of a solver that failed, or couldn't run since its input didn't load
+    pub answer: Result<String, String>,
+    pub duration: Duration,
+    pub cached: bool,
+    /// [`cache::input_hash`] of the input it ran on, `None` when it didn't load
+    pub input: Option<String>,
+}
+
+impl Run {
+    fn failed(solver: Solver, error: String, input: Option<String>) -> Self {
+        Self {
+            solver,
+            answer: Err(error),
+            duration: Duration::ZERO,
+            cached: false,
+            input,
+        }
+    }
+}
+
+/// Runs every solver of `days` (all of them if empty), optionally only for one part
+///
+/// A solver failing doesn't stop the others, its error is kept as its answer. Only answers are
+/// cached.
+pub fn run(solvers: &[Solver], days: &[u8], part: Option<u8>, mode: CacheMode) -> Result<Vec<Run>> {
+    let known = solvers.iter().map(Solver::id).collect_vec();
+    let solvers = solvers
+        .iter()
+        .filter(|solver| days.is_empty() || days.contains(&solver.day))
+        .filter(|solver| part.is_none_or(|part| part == solver.part))
+        .collect_vec();
+
+    if solvers.is_empty() {
+        return Err(eyre!("no solver for day(s) {days:?}, part {part:?}"));
+    }
+
+    let path = cache::path();
+    let mut cache = match mode {
+        CacheMode::Bypass => Cache::default(),
+        CacheMode::Refresh | CacheMode::Cached => Cache::load(&path)?,
+    };
+    cache.prune(&known);
+
+    let mut runs = vec![];
+
+    for (day, solvers) in &solvers.into_iter().group_by(|solver| solver.day) {
+        let solvers = solvers.collect_vec();
+        let input = match common::input::load(day_dir(day), solvers[0].example) {
+            Ok(input) => input,
+            Err(err) => {
+                let error = format!("can't load the input: {err}");
+                runs.extend(
+                    solvers
+                        .into_iter()
+                        .map(|solver| Run::failed(*solver, error.clone(), None)),
+                );
+                continue;
+            }
+        };
+        let input_hash = Some(cache::input_hash(&input));
+
+        for solver in solvers {
+            let id = solver.id();
+            let hash = cache::hash(&input, solver.version);
+
+            if mode == CacheMode::Cached {
+                if let Some(entry) = cache.get(&id, &hash) {
+                    runs.push(Run {
+                        solver: *solver,
+                        answer: Ok(entry.answer.clone()),
+                        duration: entry.duration,
+                        cached: true,
+                        input: input_hash.clone(),
+                    });
+                    continue;
+                }
+            }
+
+            let start = Instant::now();
+            let answer = match solver.solve(&input) {
+                Ok(answer) => answer,
+                Err(err) => {
+                    runs.push(Run::failed(*solver, err.to_string(), input_hash.clone()));
+                    continue;
+                }
+            };
+            let duration = start.elapsed();
+
+            cache.insert(
+                id,
+                Entry {
+                    hash,
+                    answer: answer.clone(),
+                    duration,
+                },
+            );
+
+            runs.push(Run {
+                solver: *solver,
+                answer: Ok(answer),
+                duration,
+                cached: false,
+                input: input_hash.clone(),
+            });
+        }
+    }
+
+    if mode != CacheMode::Bypass {
+        cache.save(&path)?;
+    }
+
+    Ok(runs)
+}
+
+/// All the implementations of one part of a day, run on the same input
+#[derive(Debug)]
+pub struct Comparison {
+    pub part: u8,
+    /// Fastest first
+    pub runs: Vec<Run>,
+}
+
+impl Comparison {
+    pub fn agree(&self) -> bool {
+        self.runs.iter().map(|run| &run.answer).all_equal()
+    }
+}
+
+/// Runs every implementation of `day`, bypassing the cache so the timings are fresh
+pub fn compare(solvers: &[Solver], day: u8) -> Result<Vec<Comparison>> {
+    let comparisons = run(solvers, &[day], None, CacheMode::Bypass)?
+        .into_iter()
+        .into_group_map_by(|run| run.solver.part)
+        .into_iter()
+        .sorted_by_key(|(part, _)| *part)
+        .map(|(part, mut runs)| {
+            // Failed runs took no time, they go last
+            runs.sort_by_key(|run| (run.answer.is_err(), run.duration));
+            Comparison { part, runs }
+        })
+        .collect();
+
+    Ok(comparisons)
+}
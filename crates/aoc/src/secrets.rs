@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+use common::crypt;
+use common::input::{encrypted_path, plaintext_path, workspace_root};
+
+pub fn keygen() -> Result<()> {
+    let path = crypt::key_path();
+
+    if path.exists() {
+        return Err(eyre!(
+            "{} already exists, not overwriting it",
+            path.display()
+        ));
+    }
+
+    std::fs::write(&path, crypt::generate_key() + "\n")?;
+    println!("wrote {}, keep it out of git", path.display());
+
+    Ok(())
+}
+
+/// `input.txt` -> `input.txt.enc`, for the given inputs or every day's
+pub fn encrypt(paths: Vec<PathBuf>) -> Result<()> {
+    let key = key()?;
+    let paths = if paths.is_empty() {
+        day_dirs()?
+            .iter()
+            .map(|dir| plaintext_path(dir))
+            .filter(|path| path.exists())
+            .collect()
+    } else {
+        paths
+    };
+
+    for path in paths {
+        let plaintext =
+            std::fs::read(&path).wrap_err_with(|| format!("reading {}", path.display()))?;
+        let encrypted = with_extension(&path);
+
+        std::fs::write(&encrypted, crypt::encrypt(&key, &plaintext))?;
+        println!("{} -> {}", path.display(), encrypted.display());
+    }
+
+    Ok(())
+}
+
+/// `input.txt.enc` -> `input.txt`, for the given inputs or every day's
+pub fn decrypt(paths: Vec<PathBuf>) -> Result<()> {
+    let key = key()?;
+    let paths = if paths.is_empty() {
+        day_dirs()?
+            .iter()
+            .map(|dir| encrypted_path(dir))
+            .filter(|path| path.exists())
+            .collect()
+    } else {
+        paths
+    };
+
+    for path in paths {
+        if path.extension().is_none_or(|ext| ext != crypt::EXTENSION) {
+            return Err(eyre!(
+                "{} doesn't end in .{}",
+                path.display(),
+                crypt::EXTENSION
+            ));
+        }
+
+        let data = std::fs::read(&path).wrap_err_with(|| format!("reading {}", path.display()))?;
+        let plaintext = crypt::decrypt(&key, &data)
+            .wrap_err_with(|| format!("decrypting {}", path.display()))?;
+        let decrypted = path.with_extension("");
+
+        std::fs::write(&decrypted, plaintext)?;
+        println!("{} -> {}", path.display(), decrypted.display());
+    }
+
+    Ok(())
+}
+
+fn key() -> Result<crypt::Key> {
+    let path = crypt::key_path();
+
+    crypt::read_key(&path)?.ok_or_else(|| {
+        eyre!(
+            "no key at {}, run `aoc keygen` or set AOC_KEY_FILE",
+            path.display()
+        )
+    })
+}
+
+fn with_extension(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(crypt::EXTENSION);
+
+    name.into()
+}
+
+/// Every `crates/dayN`
+fn day_dirs() -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![];
+
+    for entry in std::fs::read_dir(workspace_root().join("crates"))? {
+        let path = entry?.path();
+
+        if path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("day"))
+        {
+            dirs.push(path);
+        }
+    }
+
+    dirs.sort();
+
+    Ok(dirs)
+}
@@ -0,0 +1,96 @@
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use common::plugin;
+
+/// One way of solving one part of a day
+#[derive(Debug, Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    /// Tells apart alternative solutions of the same part, like day3's `slow` and `fast`
+    pub name: &'static str,
+    /// Version of the crate implementing it
+    pub version: &'static str,
+    pub example: &'static str,
+    /// `builtin`, or the file name of the plugin
+    pub origin: &'static str,
+    pub solve: Solve,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Solve {
+    Builtin(fn(&str) -> Result<String>),
+    /// From a library that is never unloaded
+    Plugin(plugin::SolveFn),
+}
+
+impl Solver {
+    /// `day3.p1.fast`
+    pub fn id(&self) -> String {
+        format!("day{}.p{}.{}", self.day, self.part, self.name)
+    }
+
+    pub fn solve(&self, input: &str) -> Result<String> {
+        match self.solve {
+            Solve::Builtin(solve) => solve(input),
+            Solve::Plugin(solve) => unsafe { plugin::solve(solve, input) }
+                .ok_or_else(|| eyre!("{} ({}) panicked", self.id(), self.origin)),
+        }
+    }
+}
+
+/// A trailing `?` marks solvers returning a `Result`
+macro_rules! solver {
+    ($krate:ident, $day:literal, $part:literal, $name:literal, $($solve:ident)::+ ?) => {
+        solver!(@ $krate, $day, $part, $name, |input| Ok($krate::$($solve)::+(input)?.to_string()))
+    };
+    ($krate:ident, $day:literal, $part:literal, $name:literal, $($solve:ident)::+) => {
+        solver!(@ $krate, $day, $part, $name, |input| Ok($krate::$($solve)::+(input).to_string()))
+    };
+    (@ $krate:ident, $day:literal, $part:literal, $name:literal, $solve:expr) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: $name,
+            version: $krate::VERSION,
+            example: $krate::EXAMPLE,
+            origin: "builtin",
+            solve: Solve::Builtin($solve),
+        }
+    };
+}
+
+pub const SOLVERS: &[Solver] = &[
+    solver!(day1, 1, 1, "default", p1?),
+    solver!(day1, 1, 1, "stream", stream::p1?),
+    solver!(day1, 1, 1, "parallel", parallel::p1?),
+    solver!(day1, 1, 2, "default", p2?),
+    solver!(day1, 1, 2, "stream", stream::p2?),
+    solver!(day1, 1, 2, "parallel", parallel::p2?),
+    solver!(day2, 2, 1, "default", p1?),
+    solver!(day2, 2, 1, "fast", fast::p1?),
+    solver!(day2, 2, 2, "default", p2?),
+    solver!(day2, 2, 2, "fast", fast::p2?),
+    solver!(day3, 3, 1, "slow", slow::p1),
+    solver!(day3, 3, 1, "fast", fast::p1),
+    solver!(day3, 3, 2, "slow", slow::p2),
+    solver!(day3, 3, 2, "fast", fast::p2),
+    solver!(day4, 4, 1, "default", p1),
+    solver!(day4, 4, 2, "default", p2),
+    solver!(day5, 5, 1, "default", p1),
+    solver!(day5, 5, 2, "default", p2),
+    solver!(day6, 6, 1, "default", p1),
+    solver!(day6, 6, 2, "default", p2),
+    solver!(day7, 7, 1, "default", p1),
+    solver!(day7, 7, 2, "default", p2),
+    solver!(day8, 8, 1, "default", p1),
+    solver!(day8, 8, 2, "default", p2),
+];
+
+/// The example of a builtin day, empty for days only solved by plugins
+pub fn example(day: u8) -> &'static str {
+    SOLVERS
+        .iter()
+        .find(|solver| solver.day == day)
+        .map_or("", |solver| solver.example)
+}
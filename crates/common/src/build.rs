@@ -0,0 +1,91 @@
+//! Helpers for the days' build scripts
+//!
+//! Every day's `build.rs` calls [`emit_source_hash`], so its `VERSION` changes whenever its code
+//! does and cached answers computed by an older solver are never reused.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Set at build time for every day, see [`source_hash`]
+pub const SOURCE_HASH_VAR: &str = "AOC_SOURCE_HASH";
+
+/// FNV-1a, stable across toolchains unlike `DefaultHasher`
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Hash of `Cargo.toml` and every file under `src` but the puzzle inputs
+pub fn source_hash(crate_dir: &Path) -> io::Result<String> {
+    let mut files = vec![crate_dir.join("Cargo.toml")];
+    collect(&crate_dir.join("src"), &mut files)?;
+    files.sort();
+
+    let mut hash = 0xcbf29ce484222325;
+    for file in files {
+        let relative = file.strip_prefix(crate_dir).unwrap_or(&file);
+        hash = fnv1a(hash, relative.to_string_lossy().as_bytes());
+        hash = fnv1a(hash, &[0]);
+        hash = fnv1a(hash, &std::fs::read(&file)?);
+        hash = fnv1a(hash, &[0]);
+    }
+
+    Ok(format!("{hash:016x}"))
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect(&path, files)?;
+        } else if !path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("input.txt"))
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets [`SOURCE_HASH_VAR`] for the crate being built, to be called from its `build.rs`
+pub fn emit_source_hash() {
+    let crate_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let hash = source_hash(&crate_dir).unwrap();
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rustc-env={SOURCE_HASH_VAR}={hash}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::TempDir;
+
+    #[test]
+    fn test_source_hash() {
+        let temp = TempDir::new("source-hash").unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "pub fn p1() {}\n").unwrap();
+
+        let before = source_hash(dir).unwrap();
+
+        // Inputs aren't code
+        std::fs::write(dir.join("src/input.txt"), "A Y\n").unwrap();
+        std::fs::write(dir.join("src/input.txt.enc"), "...").unwrap();
+        assert_eq!(source_hash(dir).unwrap(), before);
+
+        std::fs::write(dir.join("src/nested/fast.rs"), "pub fn p1() {}\n").unwrap();
+        let nested = source_hash(dir).unwrap();
+        assert_ne!(nested, before);
+
+        std::fs::write(dir.join("src/nested/fast.rs"), "pub fn p2() {}\n").unwrap();
+        assert_ne!(source_hash(dir).unwrap(), nested);
+    }
+}
@@ -0,0 +1,118 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+
+use crate::input::workspace_root;
+
+pub use chacha20poly1305::Key;
+
+/// Appended to the name of an input to get the name of its encrypted copy
+pub const EXTENSION: &str = "enc";
+
+/// XChaCha20 nonces are random, so they are stored in front of the ciphertext
+const NONCE_LEN: usize = 24;
+
+/// `$AOC_KEY_FILE`, or `.aoc.key` at the root of the workspace. Never commit it
+pub fn key_path() -> PathBuf {
+    std::env::var_os("AOC_KEY_FILE")
+        .map_or_else(|| workspace_root().join(".aoc.key"), PathBuf::from)
+}
+
+/// A new random key, hex encoded so it can be pasted around
+pub fn generate_key() -> String {
+    hex::encode(XChaCha20Poly1305::generate_key(&mut OsRng))
+}
+
+/// `None` when there is no key file
+pub fn read_key(path: &Path) -> Result<Option<Key>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).wrap_err_with(|| format!("reading {}", path.display())),
+    };
+
+    let bytes = hex::decode(contents.trim())
+        .wrap_err_with(|| format!("{} is not a hex encoded key", path.display()))?;
+
+    if bytes.len() != 32 {
+        return Err(eyre!(
+            "{} should hold a 32 byte key, got {} bytes",
+            path.display(),
+            bytes.len()
+        ));
+    }
+
+    Ok(Some(*Key::from_slice(&bytes)))
+}
+
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = XChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext)
+        .expect("encrypting into a Vec can't fail");
+
+    [nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Fails if the data was tampered with or encrypted with another key
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>> {
+    if data.len() < NONCE_LEN {
+        return Err(eyre!("encrypted input is too short"));
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+    XChaCha20Poly1305::new(key)
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| eyre!("can't decrypt input, wrong key?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::TempDir;
+
+    fn key() -> Key {
+        let hex = generate_key();
+        *Key::from_slice(&hex::decode(hex).unwrap())
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let key = key();
+        let data = encrypt(&key, b"A Y\nB X\nC Z\n");
+
+        assert_ne!(&data[NONCE_LEN..], b"A Y\nB X\nC Z\n");
+        assert_eq!(decrypt(&key, &data).unwrap(), b"A Y\nB X\nC Z\n");
+    }
+
+    #[test]
+    fn test_wrong_key() {
+        let data = encrypt(&key(), b"A Y\n");
+
+        assert!(decrypt(&key(), &data).is_err());
+        assert!(decrypt(&key(), b"short").is_err());
+    }
+
+    #[test]
+    fn test_read_key() {
+        let dir = TempDir::new("read-key").unwrap();
+
+        assert_eq!(read_key(&dir.join("missing")).unwrap(), None);
+
+        let path = dir.join("key");
+        let hex = generate_key();
+        std::fs::write(&path, format!("{hex}\n")).unwrap();
+        assert_eq!(
+            read_key(&path).unwrap(),
+            Some(*Key::from_slice(&hex::decode(hex).unwrap()))
+        );
+
+        std::fs::write(&path, "abcd").unwrap();
+        assert!(read_key(&path).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::Context;
+use color_eyre::Result;
+
+use crate::crypt;
+
+/// Loads the puzzle input of the crate this is called from, see [`load`]
+#[macro_export]
+macro_rules! input {
+    ($example:expr) => {
+        $crate::input::load(env!("CARGO_MANIFEST_DIR"), $example)
+    };
+}
+
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// `crates/dayN`
+pub fn day_dir(day: u8) -> PathBuf {
+    workspace_root().join(format!("crates/day{day}"))
+}
+
+/// The plain text input of a day, relative to its crate
+pub fn plaintext_path(crate_dir: &Path) -> PathBuf {
+    crate_dir.join("src/input.txt")
+}
+
+/// The encrypted input of a day, relative to its crate
+pub fn encrypted_path(crate_dir: &Path) -> PathBuf {
+    crate_dir.join(format!("src/input.txt.{}", crypt::EXTENSION))
+}
+
+/// Puzzle inputs can't be published, so only their encrypted copy is committed. In order:
+///
+/// 1. `src/input.txt`, when there's a local plain text copy
+/// 2. `src/input.txt.enc`, decrypted with the key at [`crypt::key_path`]
+/// 3. `example`, so a fresh checkout without the key still runs
+pub fn load(crate_dir: impl AsRef<Path>, example: &str) -> Result<String> {
+    load_with_key(crate_dir.as_ref(), &crypt::key_path(), example)
+}
+
+fn load_with_key(crate_dir: &Path, key_path: &Path, example: &str) -> Result<String> {
+    let name = crate_dir
+        .file_name()
+        .map_or_else(|| "input".into(), |name| name.to_string_lossy());
+
+    let plaintext = plaintext_path(crate_dir);
+    if plaintext.exists() {
+        return std::fs::read_to_string(&plaintext)
+            .wrap_err_with(|| format!("reading {}", plaintext.display()));
+    }
+
+    let encrypted = encrypted_path(crate_dir);
+    if !encrypted.exists() {
+        eprintln!(
+            "{name}: no {} nor {}, using the example",
+            plaintext.display(),
+            encrypted.display()
+        );
+        return Ok(example.to_string());
+    }
+
+    let Some(key) = crypt::read_key(key_path)? else {
+        eprintln!(
+            "{name}: no key at {} to decrypt {}, using the example",
+            key_path.display(),
+            encrypted.display()
+        );
+        return Ok(example.to_string());
+    };
+
+    let data =
+        std::fs::read(&encrypted).wrap_err_with(|| format!("reading {}", encrypted.display()))?;
+    let input = crypt::decrypt(&key, &data)
+        .wrap_err_with(|| format!("decrypting {}", encrypted.display()))?;
+
+    String::from_utf8(input).wrap_err_with(|| format!("{} is not UTF-8", encrypted.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::TempDir;
+
+    #[test]
+    fn test_load_order() {
+        let temp = TempDir::new("load").unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let key_path = dir.join("key");
+
+        // Nothing at all
+        assert_eq!(load_with_key(dir, &key_path, "example").unwrap(), "example");
+
+        // Encrypted, but no key
+        let hex = crypt::generate_key();
+        let key = *chacha20poly1305::Key::from_slice(&hex::decode(&hex).unwrap());
+        std::fs::write(encrypted_path(dir), crypt::encrypt(&key, b"secret")).unwrap();
+        assert_eq!(load_with_key(dir, &key_path, "example").unwrap(), "example");
+
+        // Encrypted, with the key
+        std::fs::write(&key_path, hex).unwrap();
+        assert_eq!(load_with_key(dir, &key_path, "example").unwrap(), "secret");
+
+        // Encrypted, with the wrong key
+        std::fs::write(&key_path, crypt::generate_key()).unwrap();
+        assert!(load_with_key(dir, &key_path, "example").is_err());
+
+        // Plain text wins
+        std::fs::write(plaintext_path(dir), "plain").unwrap();
+        assert_eq!(load_with_key(dir, &key_path, "example").unwrap(), "plain");
+    }
+}
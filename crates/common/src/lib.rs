@@ -0,0 +1,5 @@
+pub mod build;
+pub mod crypt;
+pub mod input;
+pub mod plugin;
+pub mod temp;
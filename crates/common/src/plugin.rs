@@ -0,0 +1,183 @@
+//! C ABI between `aoc` and solver plugins (shared libraries in the plugin directory)
+//!
+//! A plugin exports two symbols, both generated by [`crate::export_plugin!`]:
+//!
+//! - `aoc_plugin_abi_version() -> u32`, checked against [`ABI_VERSION`] before anything else
+//! - `aoc_plugin_solvers(len: *mut usize) -> *const PluginSolver`, its table of solvers
+
+use std::ffi::{c_char, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Bump it whenever anything in this module changes shape
+pub const ABI_VERSION: u32 = 1;
+
+pub const ABI_VERSION_SYMBOL: &[u8] = b"aoc_plugin_abi_version";
+pub const SOLVERS_SYMBOL: &[u8] = b"aoc_plugin_solvers";
+
+pub type AbiVersionFn = unsafe extern "C" fn() -> u32;
+pub type SolversFn = unsafe extern "C" fn(len: *mut usize) -> *const PluginSolver;
+
+/// Hands the answer back to the host, which copies it
+pub type WriteFn = extern "C" fn(out: *mut c_void, answer: *const u8, len: usize);
+
+/// Returns `false` if the solver panicked
+pub type SolveFn =
+    unsafe extern "C" fn(input: *const u8, len: usize, out: *mut c_void, write: WriteFn) -> bool;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PluginSolver {
+    pub day: u8,
+    pub part: u8,
+    /// NUL terminated, lives as long as the library
+    pub name: *const c_char,
+    /// NUL terminated version of the plugin crate, lives as long as the library
+    pub version: *const c_char,
+    pub solve: SolveFn,
+}
+
+// Only points to static data of the library
+unsafe impl Sync for PluginSolver {}
+
+/// Exports the given `(day, part, name, solver)` from a `cdylib`. Solvers are `fn(&str) -> impl ToString`
+///
+/// ```ignore
+/// common::export_plugin! {
+///     (6, 1, "sliding", p1),
+///     (6, 2, "sliding", p2),
+/// }
+/// ```
+#[macro_export]
+macro_rules! export_plugin {
+    ($(($day:literal, $part:literal, $name:literal, $solve:path)),* $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_abi_version() -> u32 {
+            $crate::plugin::ABI_VERSION
+        }
+
+        /// # Safety
+        ///
+        /// `len` must be valid for writes
+        #[no_mangle]
+        pub unsafe extern "C" fn aoc_plugin_solvers(len: *mut usize) -> *const $crate::plugin::PluginSolver {
+            static SOLVERS: &[$crate::plugin::PluginSolver] = &[$(
+                $crate::plugin::PluginSolver {
+                    day: $day,
+                    part: $part,
+                    name: concat!($name, "\0").as_ptr().cast(),
+                    version: concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast(),
+                    solve: {
+                        unsafe extern "C" fn solve(
+                            input: *const u8,
+                            len: usize,
+                            out: *mut ::std::ffi::c_void,
+                            write: $crate::plugin::WriteFn,
+                        ) -> bool {
+                            $crate::plugin::call($solve, input, len, out, write)
+                        }
+
+                        solve
+                    },
+                },
+            )*];
+
+            *len = SOLVERS.len();
+            SOLVERS.as_ptr()
+        }
+    };
+}
+
+/// Plugin side: runs a plain solver behind the C ABI, used by [`crate::export_plugin!`]
+///
+/// # Safety
+///
+/// `input` must point to `len` bytes of UTF-8
+pub unsafe fn call<T: ToString>(
+    solver: impl Fn(&str) -> T,
+    input: *const u8,
+    len: usize,
+    out: *mut c_void,
+    write: WriteFn,
+) -> bool {
+    let input = std::str::from_utf8_unchecked(std::slice::from_raw_parts(input, len));
+
+    // Unwinding into the host is undefined behaviour
+    match catch_unwind(AssertUnwindSafe(|| solver(input).to_string())) {
+        Ok(answer) => {
+            write(out, answer.as_ptr(), answer.len());
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Host side: calls a plugin solver, `None` if it panicked
+///
+/// # Safety
+///
+/// `solve` must come from a loaded library implementing [`ABI_VERSION`]
+pub unsafe fn solve(solve: SolveFn, input: &str) -> Option<String> {
+    extern "C" fn write(out: *mut c_void, answer: *const u8, len: usize) {
+        let out = unsafe { &mut *out.cast::<String>() };
+        let answer = unsafe { std::slice::from_raw_parts(answer, len) };
+
+        out.push_str(&String::from_utf8_lossy(answer));
+    }
+
+    let mut answer = String::new();
+    let ok = solve(
+        input.as_ptr(),
+        input.len(),
+        std::ptr::addr_of_mut!(answer).cast(),
+        write,
+    );
+
+    ok.then_some(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    fn count_lines(input: &str) -> usize {
+        input.lines().count()
+    }
+
+    fn explode(_input: &str) -> usize {
+        panic!("boom")
+    }
+
+    export_plugin! {
+        (1, 1, "lines", count_lines),
+        (1, 2, "explode", explode),
+    }
+
+    #[test]
+    fn test_exported_solvers() {
+        assert_eq!(aoc_plugin_abi_version(), ABI_VERSION);
+
+        let mut len = 0;
+        let solvers = unsafe {
+            let ptr = aoc_plugin_solvers(&mut len);
+            std::slice::from_raw_parts(ptr, len)
+        };
+
+        assert_eq!(solvers.len(), 2);
+        assert_eq!((solvers[0].day, solvers[0].part), (1, 1));
+        assert_eq!(unsafe { CStr::from_ptr(solvers[0].name) }, c"lines");
+        assert_eq!(
+            unsafe { CStr::from_ptr(solvers[0].version) }
+                .to_str()
+                .unwrap(),
+            env!("CARGO_PKG_VERSION")
+        );
+
+        assert_eq!(
+            unsafe { solve(solvers[0].solve, "a\nb\nc\n") },
+            Some("3".to_string())
+        );
+        assert_eq!(unsafe { solve(solvers[1].solve, "a\n") }, None);
+    }
+}
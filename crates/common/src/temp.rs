@@ -0,0 +1,58 @@
+//! Scratch directories for tests, removed when dropped even if the test panics
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A fresh directory under the system's temporary one
+#[derive(Debug)]
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Named after `label`, the process and a counter, so tests running at the same time never
+    /// share one
+    pub fn new(label: &str) -> std::io::Result<Self> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "aoc-{label}-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path)?;
+
+        Ok(Self(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // Nothing to do about a directory that can't be removed
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_dir() {
+        let (a, b) = (TempDir::new("temp").unwrap(), TempDir::new("temp").unwrap());
+        assert_ne!(a.path(), b.path());
+        assert!(a.path().is_dir());
+
+        let path = a.path().to_path_buf();
+        std::fs::write(a.join("file"), "").unwrap();
+        drop(a);
+
+        assert!(!path.exists());
+    }
+}
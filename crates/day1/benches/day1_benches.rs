@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day1::{parallel, stream};
+
+fn day1_benches(c: &mut Criterion) {
+    let input = common::input!(day1::EXAMPLE).unwrap();
+    // A huge generated inventory, where splitting the work pays off. Each copy ends with a blank
+    // line so its last elf isn't merged with the next copy's first
+    let huge = format!("{}\n\n", input.trim_end()).repeat(1000);
+
+    let mut group = c.benchmark_group("day1.p2");
+    group.bench_function("default", |b| {
+        b.iter(|| day1::p2(black_box(&huge)));
+    });
+
+    group.bench_function("stream", |b| {
+        b.iter(|| stream::p2(black_box(&huge)));
+    });
+
+    group.bench_function("parallel", |b| {
+        b.iter(|| parallel::p2(black_box(&huge)));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, day1_benches);
+criterion_main!(benches);
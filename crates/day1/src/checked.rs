@@ -0,0 +1,252 @@
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+
+use itertools::Itertools;
+
+use crate::parser::{self, Options};
+
+/// Integer types calories can be totalled in, `u128` for inventories that don't fit in 64 bits
+pub trait Total: Copy + Ord + Default {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn parse(s: &str) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_total {
+    ($($ty:ty),*) => {
+        $(
+            impl Total for $ty {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, other)
+                }
+
+                fn parse(s: &str) -> Result<Self, ParseIntError> {
+                    s.parse()
+                }
+            }
+        )*
+    };
+}
+
+impl_total!(u32, u64, u128, usize);
+
+/// Elves and lines start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    InvalidItem {
+        elf: usize,
+        line: usize,
+        item: String,
+    },
+    ItemOverflow {
+        elf: usize,
+        line: usize,
+        item: String,
+        ty: &'static str,
+    },
+    TotalOverflow {
+        elf: usize,
+        line: usize,
+        ty: &'static str,
+    },
+    /// Summing the top elves together overflowed
+    AnswerOverflow { k: usize, ty: &'static str },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidItem { elf, line, item } => {
+                write!(f, "elf {elf}, line {line}: {item:?} is not calories")
+            }
+            Self::ItemOverflow {
+                elf,
+                line,
+                item,
+                ty,
+            } => write!(f, "elf {elf}, line {line}: {item} doesn't fit in {ty}"),
+            Self::TotalOverflow { elf, line, ty } => {
+                write!(f, "elf {elf}, line {line}: total overflows {ty}")
+            }
+            Self::AnswerOverflow { k, ty } => {
+                write!(f, "the sum of the top {k} elves overflows {ty}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+    /// The same error, for an input starting after `elves` elves and `lines` lines
+    pub(crate) fn offset(self, elves: usize, lines: usize) -> Self {
+        match self {
+            Self::InvalidItem { elf, line, item } => Self::InvalidItem {
+                elf: elf + elves,
+                line: line + lines,
+                item,
+            },
+            Self::ItemOverflow {
+                elf,
+                line,
+                item,
+                ty,
+            } => Self::ItemOverflow {
+                elf: elf + elves,
+                line: line + lines,
+                item,
+                ty,
+            },
+            Self::TotalOverflow { elf, line, ty } => Self::TotalOverflow {
+                elf: elf + elves,
+                line: line + lines,
+                ty,
+            },
+            Self::AnswerOverflow { .. } => self,
+        }
+    }
+}
+
+/// Parses one item, `elf` and `line` are only used for errors
+///
+/// Only digits are calories, the `+` integers may start with isn't accepted.
+pub fn item<T: Total>(calories: &str, elf: usize, line: usize) -> Result<T, Error> {
+    if !calories.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(Error::InvalidItem {
+            elf,
+            line,
+            item: calories.to_string(),
+        });
+    }
+
+    T::parse(calories).map_err(|err| match err.kind() {
+        IntErrorKind::PosOverflow => Error::ItemOverflow {
+            elf,
+            line,
+            item: calories.to_string(),
+            ty: std::any::type_name::<T>(),
+        },
+        _ => Error::InvalidItem {
+            elf,
+            line,
+            item: calories.to_string(),
+        },
+    })
+}
+
+/// Adds an item to the total of an elf, `elf` and `line` are only used for errors
+pub fn add<T: Total>(total: T, calories: T, elf: usize, line: usize) -> Result<T, Error> {
+    total
+        .checked_add(calories)
+        .ok_or_else(|| Error::TotalOverflow {
+            elf,
+            line,
+            ty: std::any::type_name::<T>(),
+        })
+}
+
+/// Total of every elf, in input order. Blank lines without items in between don't count as elves
+pub fn totals<T: Total>(input: &str) -> Result<Vec<T>, Error> {
+    totals_with(input, &Options::default())
+}
+
+/// Same as [`totals`], parsing with `options`
+pub fn totals_with<T: Total>(input: &str, options: &Options) -> Result<Vec<T>, Error> {
+    parser::parse(input, options).map(|parsed| parsed.totals)
+}
+
+/// Calories carried by the `k` elves carrying the most, together
+pub fn top<T: Total>(input: &str, k: usize) -> Result<T, Error> {
+    top_with(input, k, &Options::default())
+}
+
+/// Same as [`top`], parsing with `options`
+pub fn top_with<T: Total>(input: &str, k: usize, options: &Options) -> Result<T, Error> {
+    totals_with::<T>(input, options)?
+        .into_iter()
+        .sorted()
+        .rev()
+        .take(k)
+        .try_fold(T::default(), T::checked_add)
+        .ok_or(Error::AnswerOverflow {
+            k,
+            ty: std::any::type_name::<T>(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[test]
+    fn test_top() {
+        assert_eq!(top::<u32>(EXAMPLE, 1), Ok(24000));
+        assert_eq!(top::<u128>(EXAMPLE, 3), Ok(45000));
+    }
+
+    #[test]
+    fn test_total_overflow() {
+        let input = "1\n\n4000000000\n300000000\n";
+
+        assert_eq!(
+            totals::<u32>(input),
+            Err(Error::TotalOverflow {
+                elf: 2,
+                line: 4,
+                ty: "u32"
+            })
+        );
+        assert_eq!(totals::<u64>(input), Ok(vec![1, 4_300_000_000]));
+    }
+
+    #[test]
+    fn test_item_overflow() {
+        let input = "1\n\n\n\n2\n340282366920938463463374607431768211456\n";
+
+        assert_eq!(
+            totals::<u128>(input),
+            Err(Error::ItemOverflow {
+                elf: 2,
+                line: 6,
+                item: "340282366920938463463374607431768211456".into(),
+                ty: "u128"
+            })
+        );
+        assert_eq!(
+            totals::<u128>(input).unwrap_err().to_string(),
+            "elf 2, line 6: 340282366920938463463374607431768211456 doesn't fit in u128"
+        );
+    }
+
+    #[test]
+    fn test_invalid_item() {
+        assert_eq!(
+            totals::<usize>("1\n-2\n"),
+            Err(Error::InvalidItem {
+                elf: 1,
+                line: 2,
+                item: "-2".into()
+            })
+        );
+        assert_eq!(
+            totals::<usize>("1\n\n+5\n"),
+            Err(Error::InvalidItem {
+                elf: 2,
+                line: 3,
+                item: "+5".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_answer_overflow() {
+        let input = "4000000000\n\n4000000000\n";
+
+        assert_eq!(top::<u32>(input, 1), Ok(4_000_000_000));
+        assert_eq!(
+            top::<u32>(input, 2),
+            Err(Error::AnswerOverflow { k: 2, ty: "u32" })
+        );
+        assert_eq!(top::<u128>(input, 2), Ok(8_000_000_000));
+    }
+}
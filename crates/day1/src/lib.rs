@@ -0,0 +1,94 @@
+pub mod checked;
+pub mod live;
+pub mod parallel;
+pub mod parser;
+pub mod plan;
+pub mod report;
+pub mod select;
+pub mod stats;
+pub mod stream;
+
+/// Used when the real input isn't available
+pub const EXAMPLE: &str = indoc::indoc! {"
+    1000
+    2000
+    3000
+
+    4000
+
+    5000
+    6000
+
+    7000
+    8000
+    9000
+
+    10000
+"};
+
+/// Part of the key of cached answers, changes with the code of the crate
+pub const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("AOC_SOURCE_HASH"));
+
+/// Calories carried by the elf carrying the most
+pub fn p1(input: &str) -> Result<usize, checked::Error> {
+    checked::top(input, 1)
+}
+
+/// Calories carried by the top three elves
+pub fn p2(input: &str) -> Result<usize, checked::Error> {
+    checked::top(input, 3)
+}
+
+/// Calories of each item, grouped by elf. Blank lines without items in between don't count as elves
+pub fn parse(input: &str) -> Result<Vec<Vec<usize>>, checked::Error> {
+    parser::parse(input, &parser::Options::default()).map(|parsed| parsed.groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse("1\n\n\n2\nthree\n"),
+            Err(checked::Error::InvalidItem {
+                elf: 2,
+                line: 5,
+                item: "three".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(EXAMPLE).unwrap(),
+            vec![
+                vec![1000, 2000, 3000],
+                vec![4000],
+                vec![5000, 6000],
+                vec![7000, 8000, 9000],
+                vec![10000],
+            ]
+        );
+    }
+
+    mod p1 {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(p1(EXAMPLE), Ok(24000));
+        }
+    }
+
+    mod p2 {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(p2(EXAMPLE), Ok(45000));
+        }
+    }
+}
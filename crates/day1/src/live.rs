@@ -0,0 +1,165 @@
+//! Standings updated after every elf, for inventories piped in as they are produced
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use itertools::Itertools;
+
+use crate::stream::{self, Group};
+
+/// The `k` elves carrying the most so far, and what was read
+#[derive(Debug, Clone)]
+pub struct Standings {
+    k: usize,
+    /// Min-heap of `(total, Reverse(elf))`: on a tie the elf read first ranks higher
+    heap: BinaryHeap<Reverse<(usize, Reverse<usize>)>>,
+    leader: Option<Group>,
+    elves: usize,
+    items: usize,
+}
+
+impl Standings {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+            leader: None,
+            elves: 0,
+            items: 0,
+        }
+    }
+
+    pub fn push(&mut self, group: Group) {
+        self.elves += 1;
+        self.items += group.items;
+
+        if self.leader.is_none_or(|leader| group.total > leader.total) {
+            self.leader = Some(group);
+        }
+
+        self.heap.push(Reverse((group.total, Reverse(group.elf))));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    pub fn leader(&self) -> Option<Group> {
+        self.leader
+    }
+
+    /// `(elf, total)`, largest first
+    pub fn top(&self) -> Vec<(usize, usize)> {
+        self.heap
+            .iter()
+            .map(|&Reverse((total, Reverse(elf)))| (elf, total))
+            .sorted_by_key(|&(elf, total)| (Reverse(total), elf))
+            .collect()
+    }
+
+    /// Calories carried by the top elves together, `None` if it overflows
+    pub fn top_total(&self) -> Option<usize> {
+        self.top()
+            .iter()
+            .try_fold(0, |sum: usize, &(_, total)| sum.checked_add(total))
+    }
+}
+
+/// Final summary, once everything was read
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves   {}", self.elves)?;
+        writeln!(f, "items   {}", self.items)?;
+
+        if let Some(leader) = self.leader {
+            writeln!(f, "leader  elf {} with {}", leader.elf, leader.total)?;
+        }
+
+        let total = self
+            .top_total()
+            .map_or_else(|| "overflow".to_string(), |total| total.to_string());
+        writeln!(f, "top {}   {total}", self.k)?;
+
+        for (rank, (elf, total)) in self.top().into_iter().enumerate() {
+            writeln!(f, "  {}. elf {elf}: {total}", rank + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads groups from `reader` until its end, writing the standings to `out` after each of them
+pub fn live(reader: impl BufRead, k: usize, mut out: impl Write) -> io::Result<Standings> {
+    let mut standings = Standings::new(k);
+
+    for group in stream::groups(reader) {
+        let group = group?;
+        standings.push(group);
+
+        let leader = standings.leader().unwrap();
+        let top = standings
+            .top()
+            .iter()
+            .map(|(elf, total)| format!("{total} (elf {elf})"))
+            .join(", ");
+
+        writeln!(
+            out,
+            "elf {}: {} | leader: elf {} ({}) | top {k}: {top}",
+            group.elf, group.total, leader.elf, leader.total
+        )?;
+        out.flush()?;
+    }
+
+    Ok(standings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[test]
+    fn test_live() {
+        let mut out = vec![];
+        let standings = live(EXAMPLE.as_bytes(), 2, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            indoc::indoc! {"
+                elf 1: 6000 | leader: elf 1 (6000) | top 2: 6000 (elf 1)
+                elf 2: 4000 | leader: elf 1 (6000) | top 2: 6000 (elf 1), 4000 (elf 2)
+                elf 3: 11000 | leader: elf 3 (11000) | top 2: 11000 (elf 3), 6000 (elf 1)
+                elf 4: 24000 | leader: elf 4 (24000) | top 2: 24000 (elf 4), 11000 (elf 3)
+                elf 5: 10000 | leader: elf 4 (24000) | top 2: 24000 (elf 4), 11000 (elf 3)
+            "}
+        );
+        assert_eq!(
+            standings.to_string(),
+            indoc::indoc! {"
+                elves   5
+                items   10
+                leader  elf 4 with 24000
+                top 2   35000
+                  1. elf 4: 24000
+                  2. elf 3: 11000
+            "}
+        );
+    }
+
+    #[test]
+    fn test_ties() {
+        let mut standings = Standings::new(2);
+        for (elf, total) in [(1, 5), (2, 7), (3, 5)] {
+            standings.push(Group {
+                elf,
+                items: 1,
+                total,
+            });
+        }
+
+        assert_eq!(standings.top(), vec![(2, 7), (1, 5)]);
+        assert_eq!(standings.leader().map(|leader| leader.elf), Some(2));
+    }
+}
@@ -1,28 +1,171 @@
+use std::io;
+
+use clap::{ArgGroup, Parser};
 use color_eyre::Result;
-use itertools::Itertools;
+use day1::checked;
+use day1::live;
+use day1::parser::{self, EmptyGroups, Mode, Options};
+use day1::plan;
+use day1::report::{self, Format};
+use day1::select::{self, Scope};
+use day1::stats;
+
+// Only one of the modes runs, so at most one can be asked for
+#[derive(Debug, Parser)]
+#[command(group(
+    ArgGroup::new("mode").args(["report", "live", "plan", "select", "stats", "wide"])
+))]
+struct Cli {
+    /// Print every elf with its items, calories and rank (table, csv or json)
+    #[arg(long)]
+    report: Option<Format>,
+
+    /// Read groups from stdin as they come, printing the standings after each of them
+    #[arg(long, conflicts_with_all = ["preserve_empty", "comments", "lenient"])]
+    live: bool,
+
+    /// Elves in the standings printed by --live
+    #[arg(long, default_value_t = 3, requires = "live")]
+    top: usize,
+
+    /// Print how to move snacks between elves so the most loaded one carries as little as possible
+    #[arg(long)]
+    plan: bool,
+
+    /// Print the fewest items adding up to exactly this many calories
+    #[arg(long, value_name = "CALORIES")]
+    select: Option<usize>,
+
+    /// Only pick the items --select prints from this elf, starting at 1
+    #[arg(long, requires = "select")]
+    elf: Option<usize>,
+
+    /// Print the distribution of the totals of the elves
+    #[arg(long)]
+    stats: bool,
+
+    /// Buckets of the histogram printed by --stats
+    #[arg(long, default_value_t = 10, requires = "stats")]
+    buckets: usize,
+
+    /// Total calories in 128 bits, for inventories overflowing the native integer
+    #[arg(long)]
+    wide: bool,
+
+    /// Every blank line after the first one in a row is an elf carrying nothing
+    #[arg(long)]
+    preserve_empty: bool,
+
+    /// Skip lines starting with `#`
+    #[arg(long)]
+    comments: bool,
+
+    /// Skip lines that aren't calories instead of failing
+    #[arg(long)]
+    lenient: bool,
+}
+
+impl Cli {
+    fn options(&self) -> Options {
+        Options {
+            empty_groups: if self.preserve_empty {
+                EmptyGroups::Preserve
+            } else {
+                EmptyGroups::Collapse
+            },
+            comments: self.comments,
+            mode: if self.lenient {
+                Mode::Lenient
+            } else {
+                Mode::Strict
+            },
+        }
+    }
+}
+
+/// Items of every elf, warning about skipped lines
+fn parse(input: &str, options: &Options) -> Result<Vec<Vec<usize>>> {
+    let parsed = parser::parse(input, options)?;
+
+    if !parsed.skipped.is_empty() {
+        eprintln!(
+            "skipped {} line(s) that aren't calories: {:?}",
+            parsed.skipped.len(),
+            parsed.skipped
+        );
+    }
+
+    Ok(parsed.groups)
+}
 
 pub fn main() -> Result<()> {
     color_eyre::install()?;
-    let input = include_str!("day1.txt");
-    let lines = input.lines().collect_vec();
-
-    let groups = lines.split(|line| line.trim().is_empty()).collect_vec();
-    // println!("{groups:?}");
-
-    let answer: usize = groups
-        .iter()
-        .map(|group| {
-            group
-                .iter()
-                .map(|line| line.parse::<usize>().unwrap())
-                .sum::<usize>()
-        })
-        .sorted()
-        .rev()
-        .take(3)
-        .sum();
-
-    println!("Answer: {answer}");
+    let cli = Cli::parse();
+
+    if cli.live {
+        let standings = live::live(io::stdin().lock(), cli.top, io::stdout())?;
+        print!("\n{standings}");
+
+        return Ok(());
+    }
+
+    let input = common::input!(day1::EXAMPLE)?;
+    let options = cli.options();
+
+    if let Some(format) = cli.report {
+        let elves = report::report(&parse(&input, &options)?);
+        print!("{}", report::render(&elves, format));
+
+        return Ok(());
+    }
+
+    if cli.plan {
+        print!("{}", plan::plan(&parse(&input, &options)?));
+
+        return Ok(());
+    }
+
+    if let Some(target) = cli.select {
+        let scope = cli.elf.map_or(Scope::All, Scope::Elf);
+
+        match select::select(&parse(&input, &options)?, scope, target)? {
+            Some(items) => {
+                for item in &items {
+                    println!("elf {}: {}", item.elf, item.calories);
+                }
+
+                println!("{target} calories in {} item(s)", items.len());
+            }
+            None => println!("no items add up to {target} calories"),
+        }
+
+        return Ok(());
+    }
+
+    if cli.stats {
+        match stats::stats(&parse(&input, &options)?, cli.buckets) {
+            Some(stats) => print!("{stats}"),
+            None => println!("no elves"),
+        }
+
+        return Ok(());
+    }
+
+    if cli.wide {
+        let a1 = checked::top_with::<u128>(&input, 1, &options)?;
+        println!("a1: {a1:?}");
+
+        let a2 = checked::top_with::<u128>(&input, 3, &options)?;
+        println!("a2: {a2:?}");
+
+        return Ok(());
+    }
+
+    let a1 = checked::top_with::<usize>(&input, 1, &options)?;
+    println!("a1: {a1:?}");
+
+    let a2 = checked::top_with::<usize>(&input, 3, &options)?;
+    println!("a2: {a2:?}");
 
     Ok(())
 }
@@ -0,0 +1,218 @@
+//! Top elves of huge inventories, on every core
+//!
+//! The input is cut into one chunk per thread, each ending right after a blank line so no elf is
+//! split. Every thread keeps the top `k` of its chunk and the results are merged at the end.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::num::NonZeroUsize;
+use std::thread;
+
+use itertools::Itertools;
+
+use crate::checked;
+
+/// Same as [`crate::p1`], on every core
+pub fn p1(input: &str) -> Result<usize, checked::Error> {
+    sum(top_k(input, 1, threads()), 1)
+}
+
+/// Same as [`crate::p2`], on every core
+pub fn p2(input: &str) -> Result<usize, checked::Error> {
+    sum(top_k(input, 3, threads()), 3)
+}
+
+fn threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+fn sum(totals: Result<Vec<usize>, checked::Error>, k: usize) -> Result<usize, checked::Error> {
+    totals?
+        .into_iter()
+        .try_fold(0, usize::checked_add)
+        .ok_or(checked::Error::AnswerOverflow { k, ty: "usize" })
+}
+
+/// Totals of the `k` elves carrying the most, largest first, using up to `threads` threads
+///
+/// Errors name the same elf and line as the sequential parsers.
+pub fn top_k(input: &str, k: usize, threads: usize) -> Result<Vec<usize>, checked::Error> {
+    let chunks = chunks(input, threads);
+
+    let results = thread::scope(|scope| {
+        chunks
+            .iter()
+            .map(|chunk| scope.spawn(move || local_top_k(chunk, k)))
+            .collect_vec()
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .collect_vec()
+    });
+
+    let mut elves = 0;
+    let mut lines = 0;
+    let mut totals = vec![];
+
+    for (chunk, result) in chunks.iter().zip(results) {
+        let (top, chunk_elves) = result.map_err(|err| err.offset(elves, lines))?;
+
+        totals.extend(top);
+        elves += chunk_elves;
+        lines += chunk.bytes().filter(|&b| b == b'\n').count();
+    }
+
+    Ok(totals.into_iter().sorted().rev().take(k).collect())
+}
+
+/// The top `k` totals of a chunk, in any order, and how many elves it holds
+fn local_top_k(chunk: &str, k: usize) -> Result<(Vec<usize>, usize), checked::Error> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    let mut elves = 0;
+    let mut current = None;
+
+    let mut push = |total| {
+        heap.push(Reverse(total));
+
+        if heap.len() > k {
+            heap.pop();
+        }
+    };
+
+    for (i, line) in chunk.lines().enumerate() {
+        let calories = line.trim();
+
+        if calories.is_empty() {
+            if let Some(total) = current.take() {
+                push(total);
+                elves += 1;
+            }
+
+            continue;
+        }
+
+        let calories = checked::item(calories, elves + 1, i + 1)?;
+        current = Some(checked::add(
+            current.unwrap_or(0),
+            calories,
+            elves + 1,
+            i + 1,
+        )?);
+    }
+
+    if let Some(total) = current {
+        push(total);
+        elves += 1;
+    }
+
+    Ok((
+        heap.into_iter().map(|Reverse(total)| total).collect(),
+        elves,
+    ))
+}
+
+/// Up to `n` chunks of about the same size, all but the last ending right after a blank line
+fn chunks(input: &str, n: usize) -> Vec<&str> {
+    let size = input.len().div_ceil(n.max(1)).max(1);
+    let mut chunks = vec![];
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let end = boundary(rest, size);
+
+        chunks.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    chunks
+}
+
+/// The end of the first blank line starting at or after `from`, or the end of `input`
+fn boundary(input: &str, from: usize) -> usize {
+    let bytes = input.as_bytes();
+    let line_end = |start: usize| {
+        bytes[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |i| start + i + 1)
+    };
+
+    if from == 0 || from >= bytes.len() {
+        return bytes.len();
+    }
+
+    let mut start = line_end(from - 1);
+
+    while start < bytes.len() {
+        let end = line_end(start);
+
+        if input[start..end].trim().is_empty() {
+            return end;
+        }
+
+        start = end;
+    }
+
+    bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    /// `elves` elves with up to 5 items each, with some noise in the separators
+    fn generate(elves: usize) -> String {
+        let mut input = String::new();
+        let mut seed = 42u64;
+
+        for elf in 0..elves {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+
+            for item in 0..=(seed >> 33) % 5 {
+                input += &format!("{}\n", (seed >> (item * 7)) % 10000);
+            }
+
+            input += if elf % 7 == 0 { "\n  \n" } else { "\n" };
+        }
+
+        input
+    }
+
+    #[test]
+    fn test_same_as_sequential() {
+        let input = generate(2000);
+        let expected = crate::stream::top_k(input.as_bytes(), 10).unwrap();
+
+        for threads in [1, 2, 3, 8, 64] {
+            assert_eq!(top_k(&input, 10, threads).unwrap(), expected, "{threads}");
+        }
+
+        assert_eq!(p1(&input), crate::p1(&input));
+        assert_eq!(p2(&input), crate::p2(&input));
+        assert_eq!(p2(EXAMPLE), Ok(45000));
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input = "1\n2\n\n3\n\n4\n5\n6\n\n7";
+        let chunks = chunks(input, 4);
+
+        assert_eq!(chunks.concat(), input);
+        assert_eq!(chunks, vec!["1\n2\n\n", "3\n\n4\n5\n6\n\n", "7"]);
+    }
+
+    #[test]
+    fn test_error_lines() {
+        let lines = generate(500).lines().count();
+        let input = generate(500) + "1\n\nabc\n";
+
+        assert_eq!(
+            top_k(&input, 3, 8),
+            Err(checked::Error::InvalidItem {
+                elf: 502,
+                line: lines + 3,
+                item: "abc".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Configurable parsing of inventories, for files that aren't as tidy as the puzzle input
+
+use crate::checked::{self, Error, Total};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    pub empty_groups: EmptyGroups,
+    /// Skip lines starting with `#`, they don't separate groups either
+    pub comments: bool,
+    pub mode: Mode,
+}
+
+/// What several blank lines in a row mean
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyGroups {
+    /// A single separator
+    #[default]
+    Collapse,
+    /// Every blank line after the first one closes an elf carrying nothing
+    Preserve,
+}
+
+/// What happens to lines that aren't calories
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// An error naming the elf and the line
+    #[default]
+    Strict,
+    /// Skipped and listed in [`Parsed::skipped`]. Items overflowing the total type are still errors
+    Lenient,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parsed<T> {
+    pub groups: Vec<Vec<T>>,
+    /// Total of each group, checked while parsing
+    pub totals: Vec<T>,
+    /// Lines that aren't calories, starting at 1, in [`Mode::Lenient`]
+    pub skipped: Vec<usize>,
+}
+
+/// Items of every elf. CRLF line endings and whitespace around items are fine in any mode
+pub fn parse<T: Total>(input: &str, options: &Options) -> Result<Parsed<T>, Error> {
+    let mut parsed = Parsed {
+        groups: vec![],
+        totals: vec![],
+        skipped: vec![],
+    };
+    let mut current = vec![];
+    let mut total = T::default();
+    let mut blank_run = 0;
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if options.comments && line.starts_with('#') {
+            continue;
+        }
+
+        if line.is_empty() {
+            let preserve = options.empty_groups == EmptyGroups::Preserve && blank_run > 0;
+
+            if !current.is_empty() || preserve {
+                parsed.groups.push(std::mem::take(&mut current));
+                parsed.totals.push(std::mem::take(&mut total));
+            }
+
+            blank_run += 1;
+            continue;
+        }
+
+        blank_run = 0;
+
+        let elf = parsed.groups.len() + 1;
+        let calories = match checked::item(line, elf, i + 1) {
+            Ok(calories) => calories,
+            Err(Error::InvalidItem { .. }) if options.mode == Mode::Lenient => {
+                parsed.skipped.push(i + 1);
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        total = checked::add(total, calories, elf, i + 1)?;
+        current.push(calories);
+    }
+
+    if !current.is_empty() {
+        parsed.groups.push(current);
+        parsed.totals.push(total);
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    const MESSY: &str =
+        "# inventory\r\n1000 \r\n2000\r\n\r\n\r\n  \r\n3000\r\n# end\r\n\r\nabc\r\n4000\r\n";
+
+    #[test]
+    fn test_default_is_parse() {
+        let parsed = parse::<usize>(EXAMPLE, &Options::default()).unwrap();
+
+        assert_eq!(parsed.groups, crate::parse(EXAMPLE).unwrap());
+        assert_eq!(parsed.totals, vec![6000, 4000, 11000, 24000, 10000]);
+        assert!(parsed.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_strict() {
+        let options = Options {
+            comments: true,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            parse::<usize>(MESSY, &options),
+            Err(Error::InvalidItem {
+                elf: 3,
+                line: 10,
+                item: "abc".into()
+            })
+        );
+        assert_eq!(
+            parse::<usize>(MESSY, &Options::default()),
+            Err(Error::InvalidItem {
+                elf: 1,
+                line: 1,
+                item: "# inventory".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        let options = Options {
+            comments: true,
+            mode: Mode::Lenient,
+            ..Options::default()
+        };
+        let parsed = parse::<usize>(MESSY, &options).unwrap();
+
+        assert_eq!(
+            parsed.groups,
+            vec![vec![1000, 2000], vec![3000], vec![4000]]
+        );
+        assert_eq!(parsed.skipped, vec![10]);
+    }
+
+    #[test]
+    fn test_preserve_empty_groups() {
+        let options = Options {
+            empty_groups: EmptyGroups::Preserve,
+            comments: true,
+            mode: Mode::Lenient,
+        };
+        let parsed = parse::<u64>(MESSY, &options).unwrap();
+
+        assert_eq!(
+            parsed.groups,
+            vec![vec![1000, 2000], vec![], vec![], vec![3000], vec![4000]]
+        );
+        assert_eq!(parsed.totals, vec![3000, 0, 0, 3000, 4000]);
+    }
+
+    #[test]
+    fn test_overflow_is_not_skipped() {
+        let options = Options {
+            mode: Mode::Lenient,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            parse::<u32>("1\n\n4294967296\n", &options),
+            Err(Error::ItemOverflow {
+                elf: 2,
+                line: 3,
+                item: "4294967296".into(),
+                ty: "u32"
+            })
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Moving snacks between elves so the most loaded one carries as little as possible
+//!
+//! This is multiway number partitioning: [`exact`] explores every assignment with branch and bound
+//! and is only practical for small inventories, [`lpt`] gives each item, largest first, to the
+//! least loaded elf and reports how far it may be from the optimum.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use itertools::Itertools;
+
+/// Inventories up to this many items are planned by [`exact`], larger ones by [`lpt`]
+pub const EXACT_LIMIT: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Exact,
+    /// Longest processing time first
+    Lpt,
+}
+
+/// One snack changing hands. Elves start at 1, like in [`crate::report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub calories: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub method: Method,
+    /// Most calories carried by one elf, before moving anything
+    pub before: u128,
+    /// Calories carried by each elf once the moves are done
+    pub loads: Vec<u128>,
+    /// No plan can do better: the largest item, or the total shared evenly
+    pub lower_bound: u128,
+    pub moves: Vec<Move>,
+}
+
+impl Plan {
+    pub fn max_load(&self) -> u128 {
+        self.loads.iter().copied().max().unwrap_or_default()
+    }
+
+    /// Upper bound on how much worse than the optimum the plan is, `0.1` being 10%
+    pub fn gap(&self) -> f64 {
+        match self.lower_bound {
+            _ if self.method == Method::Exact => 0.0,
+            0 => 0.0,
+            lower_bound => (self.max_load() - lower_bound) as f64 / lower_bound as f64,
+        }
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "method       {:?}", self.method)?;
+        writeln!(f, "max before   {}", self.before)?;
+        writeln!(f, "max after    {}", self.max_load())?;
+        writeln!(f, "lower bound  {}", self.lower_bound)?;
+        writeln!(f, "gap          {:.2}%", self.gap() * 100.0)?;
+        writeln!(f, "moves        {}", self.moves.len())?;
+
+        for Move { calories, from, to } in &self.moves {
+            writeln!(f, "  {calories} from elf {from} to elf {to}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// [`exact`] for inventories up to [`EXACT_LIMIT`] items, [`lpt`] otherwise
+pub fn plan(groups: &[Vec<usize>]) -> Plan {
+    let items = groups.iter().map(Vec::len).sum::<usize>();
+
+    if items <= EXACT_LIMIT {
+        exact(groups)
+    } else {
+        lpt(groups)
+    }
+}
+
+/// Optimal plan, exponential in the number of items
+pub fn exact(groups: &[Vec<usize>]) -> Plan {
+    let inventory = Inventory::new(groups);
+    let mut best = lpt_bins(&inventory);
+
+    if max_load(&inventory, &best) > inventory.lower_bound {
+        let mut search = Search {
+            inventory: &inventory,
+            loads: vec![0; inventory.elves],
+            bins: vec![0; inventory.items.len()],
+            best_load: max_load(&inventory, &best),
+            best: best.clone(),
+        };
+
+        search.place(0);
+        best = search.best;
+    }
+
+    inventory.plan(Method::Exact, &best)
+}
+
+/// Largest item first, each to the least loaded elf. At most 4/3 of the optimum
+pub fn lpt(groups: &[Vec<usize>]) -> Plan {
+    let inventory = Inventory::new(groups);
+    let bins = lpt_bins(&inventory);
+
+    inventory.plan(Method::Lpt, &bins)
+}
+
+/// Every item with the elf carrying it, largest first
+struct Inventory {
+    elves: usize,
+    /// `(calories, elf)`, elves starting at 0
+    items: Vec<(usize, usize)>,
+    lower_bound: u128,
+}
+
+impl Inventory {
+    /// Empty groups are elves carrying nothing, who can still be given snacks
+    fn new(groups: &[Vec<usize>]) -> Self {
+        let items = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(elf, group)| group.iter().map(move |&calories| (calories, elf)))
+            .sorted_by_key(|&(calories, elf)| (Reverse(calories), elf))
+            .collect_vec();
+
+        let total = items
+            .iter()
+            .map(|&(calories, _)| calories as u128)
+            .sum::<u128>();
+        let largest = items.first().map_or(0, |&(calories, _)| calories as u128);
+        let lower_bound = match groups.len() {
+            0 => 0,
+            elves => largest.max(total.div_ceil(elves as u128)),
+        };
+
+        Self {
+            elves: groups.len(),
+            items,
+            lower_bound,
+        }
+    }
+
+    /// Turns bins (the item at `i` goes to bin `bins[i]`) into moves between elves
+    ///
+    /// Bins are interchangeable, so each goes to the elf already holding most of its items,
+    /// greedily, to keep the moves few.
+    fn plan(&self, method: Method, bins: &[usize]) -> Plan {
+        let mut kept = vec![vec![0; self.elves]; self.elves];
+        for (&(_, elf), &bin) in self.items.iter().zip(bins) {
+            kept[bin][elf] += 1;
+        }
+
+        let mut owners = vec![None; self.elves];
+        let mut taken = vec![false; self.elves];
+
+        let pairs = (0..self.elves)
+            .cartesian_product(0..self.elves)
+            .sorted_by_key(|&(bin, elf)| (Reverse(kept[bin][elf]), bin, elf));
+
+        for (bin, elf) in pairs {
+            if owners[bin].is_none() && !taken[elf] {
+                owners[bin] = Some(elf);
+                taken[elf] = true;
+            }
+        }
+
+        let owners = owners.into_iter().map(Option::unwrap).collect_vec();
+
+        let mut before = vec![0; self.elves];
+        let mut loads = vec![0; self.elves];
+        let mut moves = vec![];
+
+        for (&(calories, from), &bin) in self.items.iter().zip(bins) {
+            let to = owners[bin];
+
+            before[from] += calories as u128;
+            loads[to] += calories as u128;
+
+            if from != to {
+                moves.push(Move {
+                    calories,
+                    from: from + 1,
+                    to: to + 1,
+                });
+            }
+        }
+
+        moves.sort_by_key(|m| (m.from, m.to, Reverse(m.calories)));
+
+        Plan {
+            method,
+            before: before.into_iter().max().unwrap_or_default(),
+            loads,
+            lower_bound: self.lower_bound,
+            moves,
+        }
+    }
+}
+
+fn lpt_bins(inventory: &Inventory) -> Vec<usize> {
+    let mut loads: BinaryHeap<_> = (0..inventory.elves).map(|bin| Reverse((0, bin))).collect();
+
+    inventory
+        .items
+        .iter()
+        .map(|&(calories, _)| {
+            let Reverse((load, bin)) = loads.pop().unwrap();
+            loads.push(Reverse((load + calories as u128, bin)));
+
+            bin
+        })
+        .collect()
+}
+
+fn max_load(inventory: &Inventory, bins: &[usize]) -> u128 {
+    let mut loads = vec![0; inventory.elves];
+    for (&(calories, _), &bin) in inventory.items.iter().zip(bins) {
+        loads[bin] += calories as u128;
+    }
+
+    loads.into_iter().max().unwrap_or_default()
+}
+
+struct Search<'a> {
+    inventory: &'a Inventory,
+    loads: Vec<u128>,
+    bins: Vec<usize>,
+    best_load: u128,
+    best: Vec<usize>,
+}
+
+impl Search<'_> {
+    fn place(&mut self, i: usize) {
+        if self.best_load == self.inventory.lower_bound {
+            return;
+        }
+
+        let Some(&(calories, _)) = self.inventory.items.get(i) else {
+            self.best_load = self.loads.iter().copied().max().unwrap_or_default();
+            self.best.clone_from(&self.bins);
+            return;
+        };
+
+        let mut tried = vec![];
+
+        for bin in 0..self.inventory.elves {
+            let load = self.loads[bin] + calories as u128;
+
+            // Bins with the same load lead to the same plans, up to swapping them
+            if load >= self.best_load || tried.contains(&self.loads[bin]) {
+                continue;
+            }
+
+            tried.push(self.loads[bin]);
+
+            self.loads[bin] = load;
+            self.bins[i] = bin;
+            self.place(i + 1);
+            self.loads[bin] -= calories as u128;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, EXAMPLE};
+
+    #[test]
+    fn test_example() {
+        let groups = parse(EXAMPLE).unwrap();
+
+        for plan in [exact(&groups), lpt(&groups)] {
+            assert_eq!(plan.before, 24000);
+            assert_eq!(plan.loads, vec![11000; 5]);
+            assert_eq!(plan.gap(), 0.0);
+        }
+
+        assert_eq!(plan(&groups).method, Method::Exact);
+    }
+
+    #[test]
+    fn test_lpt_is_not_optimal() {
+        let groups = vec![vec![3, 3, 2], vec![2, 2]];
+
+        let lpt = lpt(&groups);
+        assert_eq!(lpt.max_load(), 7);
+        assert!((lpt.gap() - 1.0 / 6.0).abs() < 1e-9);
+
+        let exact = exact(&groups);
+        assert_eq!(exact.max_load(), 6);
+        assert_eq!(exact.gap(), 0.0);
+        assert_eq!(
+            exact.moves,
+            vec![Move {
+                calories: 2,
+                from: 1,
+                to: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_moves() {
+        let plan = exact(&[vec![5, 4, 1], vec![3], vec![2]]);
+
+        assert_eq!(plan.loads, vec![5, 5, 5]);
+        assert_eq!(
+            plan.moves,
+            vec![
+                Move {
+                    calories: 5,
+                    from: 1,
+                    to: 3
+                },
+                Move {
+                    calories: 2,
+                    from: 3,
+                    to: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_elves() {
+        // A preserved empty group is an elf who can take snacks
+        let plan = exact(&[vec![4, 4], vec![]]);
+
+        assert_eq!(plan.loads, vec![4, 4]);
+        assert_eq!(
+            plan.moves,
+            vec![Move {
+                calories: 4,
+                from: 1,
+                to: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_nothing_to_plan() {
+        let plan = plan(&[]);
+
+        assert_eq!(plan.max_load(), 0);
+        assert!(plan.moves.is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+/// Serialized with the columns of the table and csv formats, in the same order
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Elf {
+    /// 1 is the elf carrying the most
+    pub rank: usize,
+    /// Position in the input, starting at 1
+    #[serde(rename = "elf")]
+    pub index: usize,
+    pub items: usize,
+    /// Summing `usize`s that fit in memory can't overflow a `u128`
+    #[serde(rename = "calories")]
+    pub total: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format {s:?}, expected table, csv or json")),
+        }
+    }
+}
+
+/// Every elf, by rank. Elves carrying the same total are ranked by their position in the input
+///
+/// Empty groups, only kept by [`crate::parser::EmptyGroups::Preserve`], are elves carrying nothing
+pub fn report(groups: &[Vec<usize>]) -> Vec<Elf> {
+    groups
+        .iter()
+        .enumerate()
+        .map(|(i, group)| {
+            let total = group.iter().map(|&calories| calories as u128).sum::<u128>();
+            (i + 1, group.len(), total)
+        })
+        .sorted_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)))
+        .enumerate()
+        .map(|(rank, (index, items, total))| Elf {
+            index,
+            items,
+            total,
+            rank: rank + 1,
+        })
+        .collect()
+}
+
+pub fn render(elves: &[Elf], format: Format) -> String {
+    match format {
+        Format::Table => table(elves),
+        Format::Csv => csv(elves),
+        Format::Json => serde_json::to_string_pretty(elves).unwrap() + "\n",
+    }
+}
+
+fn table(elves: &[Elf]) -> String {
+    let rows = elves
+        .iter()
+        .map(|elf| {
+            vec![
+                elf.rank.to_string(),
+                elf.index.to_string(),
+                elf.items.to_string(),
+                elf.total.to_string(),
+            ]
+        })
+        .collect_vec();
+
+    let header = ["rank", "elf", "items", "calories"];
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([title.len()])
+                .max()
+                .unwrap()
+        })
+        .collect_vec();
+
+    let mut out = String::new();
+    let header = header.iter().map(ToString::to_string).collect_vec();
+
+    for row in [header].iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:>width$}"))
+            .join("  ");
+
+        writeln!(out, "{line}").unwrap();
+    }
+
+    out
+}
+
+fn csv(elves: &[Elf]) -> String {
+    let mut out = String::from("rank,elf,items,calories\n");
+
+    for elf in elves {
+        writeln!(
+            out,
+            "{},{},{},{}",
+            elf.rank, elf.index, elf.items, elf.total
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, EmptyGroups, Options};
+    use crate::{parse, EXAMPLE};
+
+    #[test]
+    fn test_report() {
+        let elves = report(&parse(EXAMPLE).unwrap());
+
+        assert_eq!(
+            elves.iter().map(|elf| (elf.rank, elf.index)).collect_vec(),
+            vec![(1, 4), (2, 3), (3, 5), (4, 1), (5, 2)]
+        );
+        assert_eq!(
+            elves[0],
+            Elf {
+                index: 4,
+                items: 3,
+                total: 24000,
+                rank: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_ties() {
+        let elves = report(&parse("5\n\n2\n3\n\n\n\n4\n1\n\n6\n").unwrap());
+
+        assert_eq!(
+            elves
+                .iter()
+                .map(|elf| (elf.rank, elf.index, elf.total))
+                .collect_vec(),
+            vec![(1, 4, 6), (2, 1, 5), (3, 2, 5), (4, 3, 5)]
+        );
+    }
+
+    #[test]
+    fn test_preserved_empty_groups() {
+        let options = Options {
+            empty_groups: EmptyGroups::Preserve,
+            ..Options::default()
+        };
+        let groups = parser::parse::<usize>("5\n\n\n2\n", &options)
+            .unwrap()
+            .groups;
+        let elves = report(&groups);
+
+        assert_eq!(
+            elves
+                .iter()
+                .map(|elf| (elf.rank, elf.index, elf.total))
+                .collect_vec(),
+            vec![(1, 1, 5), (2, 3, 2), (3, 2, 0)]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let elves = report(&parse("100\n200\n\n50\n").unwrap());
+
+        assert_eq!(
+            render(&elves, Format::Table),
+            indoc::indoc! {"
+                rank  elf  items  calories
+                   1    1      2       300
+                   2    2      1        50
+            "}
+        );
+
+        assert_eq!(
+            render(&elves, Format::Csv),
+            indoc::indoc! {"
+                rank,elf,items,calories
+                1,1,2,300
+                2,2,1,50
+            "}
+        );
+
+        let json = render(&elves, Format::Json);
+        // Same names and order as the other formats' columns
+        assert!(json.starts_with(
+            "[\n  {\n    \"rank\": 1,\n    \"elf\": 1,\n    \"items\": 2,\n    \"calories\": 300\n"
+        ));
+
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "rank": 1, "elf": 1, "items": 2, "calories": 300 },
+                { "rank": 2, "elf": 2, "items": 1, "calories": 50 },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
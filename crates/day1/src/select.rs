@@ -0,0 +1,277 @@
+//! Which snacks add up to exactly some calories, with as few items as possible
+//!
+//! A subset sum solved by dynamic programming over the sums up to the target. Items and the
+//! target are first divided by their greatest common divisor, so round calories stay cheap. The
+//! table keeps a bit per item and sum, so it's capped at [`TABLE_LIMIT`] bits.
+
+use std::fmt;
+
+/// Where items can be picked from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Only the elf at this position, starting at 1
+    Elf(usize),
+    All,
+}
+
+/// One picked snack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    /// Starting at 1, empty groups count as elves
+    pub elf: usize,
+    pub calories: usize,
+}
+
+/// Most bits the table may hold, 128 MiB: items times sums up to the target, both reduced
+pub const TABLE_LIMIT: usize = 1 << 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    UnknownElf(usize),
+    /// Picking among `items` items up to `target` calories needs more than [`TABLE_LIMIT`] bits
+    TooLarge {
+        items: usize,
+        target: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownElf(elf) => write!(f, "there is no elf {elf}"),
+            Self::TooLarge { items, target } => write!(
+                f,
+                "picking among {items} items up to {target} calories needs more than {TABLE_LIMIT} bits"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The fewest items of `scope` adding up to exactly `target`, largest first
+///
+/// `None` if no selection adds up to `target`.
+pub fn select(
+    groups: &[Vec<usize>],
+    scope: Scope,
+    target: usize,
+) -> Result<Option<Vec<Item>>, Error> {
+    let elves = groups.iter();
+    let items: Vec<Item> = match scope {
+        Scope::All => elves
+            .enumerate()
+            .flat_map(|(i, group)| items_of(i + 1, group))
+            .collect(),
+        Scope::Elf(elf) => elf
+            .checked_sub(1)
+            .and_then(|i| elves.clone().nth(i))
+            .map(|group| items_of(elf, group).collect())
+            .ok_or(Error::UnknownElf(elf))?,
+    };
+
+    fewest(items, target)
+}
+
+fn items_of(elf: usize, group: &[usize]) -> impl Iterator<Item = Item> + '_ {
+    group.iter().map(move |&calories| Item { elf, calories })
+}
+
+fn fewest(mut items: Vec<Item>, target: usize) -> Result<Option<Vec<Item>>, Error> {
+    if target == 0 {
+        return Ok(Some(vec![]));
+    }
+
+    items.retain(|item| item.calories <= target);
+
+    // The table below is sized from the target, so a target past everything the items add up to
+    // must be rejected before it's allocated
+    let total = items
+        .iter()
+        .try_fold(0usize, |total, item| total.checked_add(item.calories));
+    if total.is_some_and(|total| total < target) {
+        return Ok(None);
+    }
+
+    items.sort_by_key(|item| (std::cmp::Reverse(item.calories), item.elf));
+
+    let divisor = items
+        .iter()
+        .fold(target, |gcd, item| gcd_of(gcd, item.calories));
+    let reduced = target / divisor;
+
+    if (reduced + 1)
+        .checked_mul(items.len())
+        .is_none_or(|bits| bits > TABLE_LIMIT)
+    {
+        return Err(Error::TooLarge {
+            items: items.len(),
+            target,
+        });
+    }
+
+    let target = reduced;
+
+    // `counts[sum]` is the fewest items adding up to `sum` so far, `taken[i]` the sums for which
+    // item `i` improved it
+    let mut counts = vec![u32::MAX; target + 1];
+    let mut taken = vec![Bits::new(target + 1); items.len()];
+
+    counts[0] = 0;
+
+    for (i, item) in items.iter().enumerate() {
+        let calories = item.calories / divisor;
+
+        for sum in (calories.max(1)..=target).rev() {
+            let count = counts[sum - calories].saturating_add(1);
+
+            if count < counts[sum] {
+                counts[sum] = count;
+                taken[i].set(sum);
+            }
+        }
+    }
+
+    if counts[target] == u32::MAX {
+        return Ok(None);
+    }
+
+    let mut sum = target;
+    let mut selection = vec![];
+
+    for (i, item) in items.iter().enumerate().rev() {
+        if sum > 0 && taken[i].get(sum) {
+            selection.push(*item);
+            sum -= item.calories / divisor;
+        }
+    }
+
+    selection.reverse();
+
+    Ok(Some(selection))
+}
+
+fn gcd_of(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        b => gcd_of(b, a % b),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.0[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn set(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, EXAMPLE};
+
+    fn calories(selection: Option<Vec<Item>>) -> Option<Vec<usize>> {
+        selection.map(|items| items.iter().map(|item| item.calories).collect())
+    }
+
+    #[test]
+    fn test_all_elves() {
+        let groups = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            select(&groups, Scope::All, 17000).unwrap(),
+            Some(vec![
+                Item {
+                    elf: 4,
+                    calories: 9000
+                },
+                Item {
+                    elf: 4,
+                    calories: 8000
+                },
+            ])
+        );
+        assert_eq!(
+            calories(select(&groups, Scope::All, 54000).unwrap()),
+            Some(vec![10000, 9000, 8000, 7000, 6000, 5000, 4000, 3000, 2000])
+        );
+        assert_eq!(select(&groups, Scope::All, 55001).unwrap(), None);
+        assert_eq!(select(&groups, Scope::All, 500).unwrap(), None);
+        assert_eq!(select(&groups, Scope::All, 100_000_000_000).unwrap(), None);
+        assert_eq!(select(&groups, Scope::All, usize::MAX).unwrap(), None);
+    }
+
+    #[test]
+    fn test_single_elf() {
+        let groups = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            calories(select(&groups, Scope::Elf(1), 4000).unwrap()),
+            Some(vec![3000, 1000])
+        );
+        assert_eq!(select(&groups, Scope::Elf(2), 5000).unwrap(), None);
+        assert_eq!(
+            select(&groups, Scope::Elf(6), 5000),
+            Err(Error::UnknownElf(6))
+        );
+        assert_eq!(
+            select(&groups, Scope::Elf(0), 5000),
+            Err(Error::UnknownElf(0))
+        );
+
+        // A preserved empty group is elf 2
+        let groups = vec![vec![1], vec![], vec![2]];
+        assert_eq!(select(&groups, Scope::Elf(2), 2).unwrap(), None);
+        assert_eq!(
+            select(&groups, Scope::Elf(3), 2).unwrap(),
+            Some(vec![Item {
+                elf: 3,
+                calories: 2
+            }])
+        );
+    }
+
+    #[test]
+    fn test_table_limit() {
+        // Coprime, so nothing is divided away: 2 items times 2^29 + 2 sums is just too many
+        let groups = vec![vec![1 << 29, 1]];
+
+        assert_eq!(
+            select(&groups, Scope::All, (1 << 29) + 1),
+            Err(Error::TooLarge {
+                items: 2,
+                target: (1 << 29) + 1
+            })
+        );
+        // Round calories are divided first
+        assert_eq!(
+            calories(select(&[vec![1 << 40, 1 << 41]], Scope::All, 3 << 40).unwrap()),
+            Some(vec![1 << 41, 1 << 40])
+        );
+    }
+
+    #[test]
+    fn test_fewest_items() {
+        // Greedily taking 6 first gets stuck
+        let groups = vec![vec![6, 5, 5, 1, 1, 1, 1]];
+
+        assert_eq!(
+            calories(select(&groups, Scope::All, 10).unwrap()),
+            Some(vec![5, 5])
+        );
+        assert_eq!(
+            calories(select(&groups, Scope::All, 0).unwrap()),
+            Some(vec![])
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+/// Printed along the median by [`Stats`]
+pub const PERCENTILES: [f64; 5] = [10.0, 25.0, 75.0, 90.0, 99.0];
+
+/// Width of the longest bar of the histogram, in characters
+const BAR_WIDTH: usize = 40;
+
+/// Distribution of the totals of the elves
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub min: u128,
+    pub max: u128,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation, every elf is accounted for
+    pub std_dev: f64,
+    /// `(p, value)` for each of [`PERCENTILES`]
+    pub percentiles: Vec<(f64, f64)>,
+    pub histogram: Vec<Bucket>,
+}
+
+/// Elves carrying between `start` and `end` calories, both included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: u128,
+    pub end: u128,
+    pub count: usize,
+}
+
+/// `None` without any elf. Empty groups are elves carrying nothing, like in [`crate::report`]
+pub fn stats(groups: &[Vec<usize>], buckets: usize) -> Option<Stats> {
+    let mut totals: Vec<u128> = groups
+        .iter()
+        .map(|group| group.iter().map(|&calories| calories as u128).sum())
+        .collect();
+
+    totals.sort_unstable();
+
+    let count = totals.len();
+    let min = *totals.first()?;
+    let max = *totals.last()?;
+
+    let mean = totals.iter().map(|&total| total as f64).sum::<f64>() / count as f64;
+    let variance = totals
+        .iter()
+        .map(|&total| (total as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count as f64;
+
+    Some(Stats {
+        count,
+        min,
+        max,
+        mean,
+        median: percentile(&totals, 50.0),
+        std_dev: variance.sqrt(),
+        percentiles: PERCENTILES
+            .iter()
+            .map(|&p| (p, percentile(&totals, p)))
+            .collect(),
+        histogram: histogram(&totals, buckets),
+    })
+}
+
+/// `p`th percentile of sorted, non-empty `totals`, interpolating linearly between the closest ranks
+pub fn percentile(totals: &[u128], p: f64) -> f64 {
+    let rank = p.clamp(0.0, 100.0) / 100.0 * (totals.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - below as f64;
+
+    totals[below] as f64 * (1.0 - weight) + totals[above] as f64 * weight
+}
+
+/// At most `buckets` buckets of the same width, from the smallest to the largest of sorted `totals`
+pub fn histogram(totals: &[u128], buckets: usize) -> Vec<Bucket> {
+    let (Some(&min), Some(&max)) = (totals.first(), totals.last()) else {
+        return vec![];
+    };
+
+    let width = (max - min + 1).div_ceil(buckets.max(1) as u128);
+    let mut histogram: Vec<Bucket> = (0..)
+        .map(|i| min + i * width)
+        .take_while(|&start| start <= max)
+        .map(|start| Bucket {
+            start,
+            end: (start + width - 1).min(max),
+            count: 0,
+        })
+        .collect();
+
+    for &total in totals {
+        histogram[((total - min) / width) as usize].count += 1;
+    }
+
+    histogram
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves    {}", self.count)?;
+        writeln!(f, "min      {}", self.min)?;
+        writeln!(f, "max      {}", self.max)?;
+        writeln!(f, "mean     {:.1}", self.mean)?;
+        writeln!(f, "median   {:.1}", self.median)?;
+        writeln!(f, "std dev  {:.1}", self.std_dev)?;
+
+        for (p, value) in &self.percentiles {
+            writeln!(f, "{:<9}{value:.1}", format!("p{p}"))?;
+        }
+
+        let start_width = self.max.to_string().len();
+        let tallest = self.histogram.iter().map(|bucket| bucket.count).max();
+
+        for bucket in &self.histogram {
+            let bar = bucket.count * BAR_WIDTH / tallest.unwrap_or(1);
+
+            writeln!(
+                f,
+                "{:>start_width$}..={:>start_width$} {:<BAR_WIDTH$} {}",
+                bucket.start,
+                bucket.end,
+                "#".repeat(bar),
+                bucket.count
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, EXAMPLE};
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&parse(EXAMPLE).unwrap(), 2).unwrap();
+
+        assert_eq!(stats.count, 5);
+        assert_eq!((stats.min, stats.max), (4000, 24000));
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert!(
+            (stats.std_dev - 6985.6997).abs() < 1e-3,
+            "{}",
+            stats.std_dev
+        );
+        assert_eq!(stats.percentiles[1], (25.0, 6000.0));
+        assert_eq!(
+            stats.histogram,
+            vec![
+                Bucket {
+                    start: 4000,
+                    end: 14000,
+                    count: 4
+                },
+                Bucket {
+                    start: 14001,
+                    end: 24000,
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_elves() {
+        assert_eq!(stats(&[], 10), None);
+    }
+
+    #[test]
+    fn test_empty_elves() {
+        let stats = stats(&[vec![4], vec![], vec![2]], 1).unwrap();
+
+        assert_eq!(stats.count, 3);
+        assert_eq!((stats.min, stats.max), (0, 4));
+        assert_eq!(stats.mean, 2.0);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let totals = [10, 20, 30, 40];
+
+        assert_eq!(percentile(&totals, 0.0), 10.0);
+        assert_eq!(percentile(&totals, 50.0), 25.0);
+        assert_eq!(percentile(&totals, 100.0), 40.0);
+        assert_eq!(percentile(&[7], 90.0), 7.0);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let histogram = histogram(&[1, 1, 2, 9, 10], 3);
+
+        assert_eq!(
+            histogram
+                .iter()
+                .map(|bucket| (bucket.start, bucket.end, bucket.count))
+                .collect::<Vec<_>>(),
+            vec![(1, 4, 3), (5, 8, 0), (9, 10, 2)]
+        );
+
+        // More buckets than distinct values
+        assert_eq!(histogram_len(&[5, 5], 10), 1);
+        assert_eq!(histogram_len(&[1, 3], 10), 3);
+    }
+
+    fn histogram_len(totals: &[u128], buckets: usize) -> usize {
+        histogram(totals, buckets).len()
+    }
+}
@@ -0,0 +1,195 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
+
+use crate::checked;
+
+/// Same as [`crate::p1`], without holding the input in memory
+pub fn p1(input: &str) -> io::Result<usize> {
+    sum(top_k(input.as_bytes(), 1)?, 1)
+}
+
+/// Same as [`crate::p2`], without holding the input in memory
+pub fn p2(input: &str) -> io::Result<usize> {
+    sum(top_k(input.as_bytes(), 3)?, 3)
+}
+
+fn sum(totals: Vec<usize>, k: usize) -> io::Result<usize> {
+    totals
+        .into_iter()
+        .try_fold(0, usize::checked_add)
+        .ok_or_else(|| invalid(checked::Error::AnswerOverflow { k, ty: "usize" }))
+}
+
+fn invalid(err: checked::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Totals of the `k` elves carrying the most, largest first
+///
+/// Reads one line at a time and keeps a min-heap of at most `k` totals, so memory doesn't grow
+/// with the number of elves. Blank lines without items in between don't count as elves
+pub fn top_k(reader: impl BufRead, k: usize) -> io::Result<Vec<usize>> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+
+    for group in groups(reader) {
+        heap.push(Reverse(group?.total));
+
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(total)| total)
+        .collect())
+}
+
+/// One elf, read from a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group {
+    /// Starting at 1, empty groups are not elves
+    pub elf: usize,
+    pub items: usize,
+    pub total: usize,
+}
+
+/// Every group of `reader`, each yielded as soon as the blank line (or the end) closing it is read
+pub fn groups<R: BufRead>(reader: R) -> Groups<R> {
+    Groups {
+        reader,
+        line: String::new(),
+        line_number: 0,
+        elves: 0,
+    }
+}
+
+pub struct Groups<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    elves: usize,
+}
+
+impl<R: BufRead> Groups<R> {
+    fn next_group(&mut self) -> io::Result<Option<Group>> {
+        let elf = self.elves + 1;
+        let mut items = 0;
+        let mut total = 0;
+
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                break;
+            }
+            self.line_number += 1;
+
+            let calories = self.line.trim();
+            if calories.is_empty() {
+                if items > 0 {
+                    break;
+                }
+
+                continue;
+            }
+
+            let calories = checked::item(calories, elf, self.line_number).map_err(invalid)?;
+
+            total = checked::add(total, calories, elf, self.line_number).map_err(invalid)?;
+            items += 1;
+        }
+
+        if items == 0 {
+            return Ok(None);
+        }
+
+        self.elves = elf;
+
+        Ok(Some(Group { elf, items, total }))
+    }
+}
+
+impl<R: BufRead> Iterator for Groups<R> {
+    type Item = io::Result<Group>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_group().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[test]
+    fn test_top_k() {
+        assert!(top_k(EXAMPLE.as_bytes(), 0).unwrap().is_empty());
+        assert_eq!(top_k(EXAMPLE.as_bytes(), 1).unwrap(), vec![24000]);
+        assert_eq!(
+            top_k(EXAMPLE.as_bytes(), 3).unwrap(),
+            vec![24000, 11000, 10000]
+        );
+        assert_eq!(
+            top_k(EXAMPLE.as_bytes(), 10).unwrap(),
+            vec![24000, 11000, 10000, 6000, 4000]
+        );
+    }
+
+    #[test]
+    fn test_separators() {
+        // No trailing newline, several blank lines in a row
+        assert_eq!(top_k("1\n2\n\n\n\n4".as_bytes(), 5).unwrap(), vec![4, 3]);
+    }
+
+    #[test]
+    fn test_groups() {
+        let groups: Vec<_> = groups("\n1\n2\n\n\n3\n".as_bytes())
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                Group {
+                    elf: 1,
+                    items: 2,
+                    total: 3
+                },
+                Group {
+                    elf: 2,
+                    items: 1,
+                    total: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        let err = top_k("1\n\nabc\n".as_bytes(), 3).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "elf 2, line 3: \"abc\" is not calories");
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = format!("{}\n\n1\n{}\n", usize::MAX, usize::MAX);
+        let err = top_k(input.as_bytes(), 3).unwrap_err();
+
+        assert_eq!(err.to_string(), "elf 2, line 4: total overflows usize");
+
+        let input = format!("{}\n\n1\n", usize::MAX);
+        assert!(p1(&input).is_ok());
+        assert!(p2(&input).is_err());
+    }
+
+    #[test]
+    fn test_same_as_sorting() {
+        assert_eq!(p1(EXAMPLE).ok(), crate::p1(EXAMPLE).ok());
+        assert_eq!(p2(EXAMPLE).ok(), crate::p2(EXAMPLE).ok());
+    }
+}
@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day2::fast;
+
+fn day2_benches(c: &mut Criterion) {
+    let input = common::input!(day2::EXAMPLE).unwrap();
+
+    let mut group = c.benchmark_group("day2.p1");
+    group.bench_function("default", |b| {
+        b.iter(|| day2::p1(black_box(&input)));
+    });
+
+    group.bench_function("fast", |b| {
+        b.iter(|| fast::p1(black_box(&input)));
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("day2.p2");
+    group.bench_function("default", |b| {
+        b.iter(|| day2::p2(black_box(&input)));
+    });
+
+    group.bench_function("fast", |b| {
+        b.iter(|| fast::p2(black_box(&input)));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, day2_benches);
+criterion_main!(benches);
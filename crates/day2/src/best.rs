@@ -0,0 +1,158 @@
+//! The best we could score against the opponent's column, and how far the guide is from it
+
+use std::fmt;
+
+use crate::encoding::Encoding;
+use crate::guide::{self, Round};
+use crate::scoring::ScoringRules;
+use crate::{Outcome, Symbol};
+
+/// What scores the most against `opponent` under `rules`, points that overflow counting the least
+pub fn best_response(opponent: Symbol, rules: &ScoringRules) -> Outcome {
+    Symbol::ALL
+        .into_iter()
+        .map(|ours| Outcome::from((opponent, ours)))
+        .max_by_key(|&outcome| rules.score(outcome))
+        .unwrap()
+}
+
+/// One round played the best way, and both ways the guide can be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    /// Starting at 1
+    pub line: usize,
+    pub opponent: Symbol,
+    pub best: Outcome,
+    pub p1: Outcome,
+    pub p2: Outcome,
+}
+
+impl Comparison {
+    fn new(round: &Round, encoding: &Encoding, rules: &ScoringRules) -> Result<Self, guide::Error> {
+        let opponent = round.opponent(encoding)?;
+
+        Ok(Self {
+            line: round.line,
+            opponent,
+            best: best_response(opponent, rules),
+            p1: Outcome::from((opponent, round.response(encoding)?)),
+            p2: Outcome::from((opponent, round.outcome(encoding)?)),
+        })
+    }
+}
+
+/// Scores over the whole guide
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub best: usize,
+    pub p1: usize,
+    pub p2: usize,
+}
+
+impl Totals {
+    /// Score the part 1 interpretation leaves on the table
+    pub fn p1_gap(&self) -> usize {
+        self.best - self.p1
+    }
+
+    /// Score the part 2 interpretation leaves on the table
+    pub fn p2_gap(&self) -> usize {
+        self.best - self.p2
+    }
+}
+
+impl fmt::Display for Totals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "best  {}", self.best)?;
+        writeln!(f, "p1    {} ({} left on the table)", self.p1, self.p1_gap())?;
+        writeln!(f, "p2    {} ({} left on the table)", self.p2, self.p2_gap())
+    }
+}
+
+/// Every round compared, and the totals under `rules`
+pub fn compare(
+    input: &str,
+    encoding: &Encoding,
+    rules: &ScoringRules,
+) -> Result<(Vec<Comparison>, Totals), guide::Error> {
+    let rounds = guide::rounds(input)
+        .map(|round| Comparison::new(&round?, encoding, rules))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let totals = rounds.iter().try_fold(Totals::default(), |totals, round| {
+        let add = |total: usize, outcome| total.checked_add(rules.score(outcome)?);
+        let overflow = guide::Error::Overflow { line: round.line };
+
+        Ok(Totals {
+            best: add(totals.best, round.best).ok_or(overflow.clone())?,
+            p1: add(totals.p1, round.p1).ok_or(overflow.clone())?,
+            p2: add(totals.p2, round.p2).ok_or(overflow)?,
+        })
+    })?;
+
+    Ok((rounds, totals))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[test]
+    fn test_best_response() {
+        // Winning always pays more than any shape
+        assert_eq!(
+            best_response(Symbol::Rock, &ScoringRules::PUZZLE),
+            Outcome::Win {
+                symbol: Symbol::Paper
+            }
+        );
+        assert_eq!(
+            best_response(Symbol::Paper, &ScoringRules::PUZZLE),
+            Outcome::Win {
+                symbol: Symbol::Scissors
+            }
+        );
+        assert_eq!(
+            best_response(Symbol::Scissors, &ScoringRules::PUZZLE),
+            Outcome::Win {
+                symbol: Symbol::Rock
+            }
+        );
+
+        // Unless Scissors is worth more than a win
+        let rules = ScoringRules {
+            shape: [1, 2, 10],
+            ..ScoringRules::PUZZLE
+        };
+        assert_eq!(
+            best_response(Symbol::Rock, &rules),
+            Outcome::Loss {
+                symbol: Symbol::Scissors
+            }
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let (rounds, totals) =
+            compare(EXAMPLE, &Encoding::default(), &ScoringRules::PUZZLE).unwrap();
+
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(
+            rounds[1].p2,
+            Outcome::Loss {
+                symbol: Symbol::Rock
+            }
+        );
+        assert_eq!(
+            totals,
+            Totals {
+                best: 8 + 9 + 7,
+                p1: 15,
+                p2: 12
+            }
+        );
+        assert_eq!((totals.p1_gap(), totals.p2_gap()), (9, 12));
+    }
+}
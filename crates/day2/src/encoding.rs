@@ -0,0 +1,278 @@
+//! What the letters of a strategy guide stand for
+//!
+//! Built in code with [`Encoding::new`] and [`Encoding::alias`], or parsed from a small file
+//! listing the tokens of each column in order:
+//!
+//! ```text
+//! # Rock Paper Scissors, then Loss Draw Win for the outcome
+//! opponent: A B C
+//! opponent: rock paper scissors
+//! response: X Y Z
+//! outcome: X Y Z
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::{Goal, Symbol};
+
+const GOALS: [Goal; 3] = [Goal::Lose, Goal::Draw, Goal::Win];
+
+/// The column of a guide a token is read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// First column, what the opponent plays
+    Opponent,
+    /// Second column under the part 1 interpretation, what we play
+    Response,
+    /// Second column under the part 2 interpretation, how the round should end
+    Outcome,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "opponent" => Ok(Self::Opponent),
+            "response" => Ok(Self::Response),
+            "outcome" => Ok(Self::Outcome),
+            _ => Err(format!(
+                "unknown column {s:?}, expected opponent, response or outcome"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Opponent => "opponent",
+            Self::Response => "response",
+            Self::Outcome => "outcome",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Encoding {
+    opponent: HashMap<String, Symbol>,
+    response: HashMap<String, Symbol>,
+    outcome: HashMap<String, Goal>,
+}
+
+/// The puzzle's, see [`Encoding::puzzle`]
+impl Default for Encoding {
+    fn default() -> Self {
+        Self::puzzle().clone()
+    }
+}
+
+impl Encoding {
+    /// Tokens for Rock, Paper and Scissors, then for Loss, Draw and Win
+    ///
+    /// Fails if a token is used twice in a column.
+    pub fn new(
+        opponent: [&str; 3],
+        response: [&str; 3],
+        outcome: [&str; 3],
+    ) -> Result<Self, String> {
+        let mut encoding = Self::empty();
+
+        for (column, tokens) in [
+            (Column::Opponent, opponent),
+            (Column::Response, response),
+            (Column::Outcome, outcome),
+        ] {
+            encoding.alias(column, tokens)?;
+        }
+
+        Ok(encoding)
+    }
+
+    /// The puzzle's: `A B C` for the opponent, `X Y Z` for the second column
+    pub fn puzzle() -> &'static Self {
+        static PUZZLE: OnceLock<Encoding> = OnceLock::new();
+
+        PUZZLE.get_or_init(|| Self::new(["A", "B", "C"], ["X", "Y", "Z"], ["X", "Y", "Z"]).unwrap())
+    }
+
+    fn empty() -> Self {
+        Self {
+            opponent: HashMap::new(),
+            response: HashMap::new(),
+            outcome: HashMap::new(),
+        }
+    }
+
+    /// More tokens for a column, in the same order as [`Encoding::new`]
+    ///
+    /// Fails if one of them already stands for something else in that column.
+    pub fn alias(&mut self, column: Column, tokens: [&str; 3]) -> Result<(), String> {
+        match column {
+            Column::Opponent => insert(&mut self.opponent, column, tokens, Symbol::ALL),
+            Column::Response => insert(&mut self.response, column, tokens, Symbol::ALL),
+            Column::Outcome => insert(&mut self.outcome, column, tokens, GOALS),
+        }
+    }
+
+    pub fn opponent(&self, token: &str) -> Option<Symbol> {
+        self.opponent.get(token).copied()
+    }
+
+    pub fn response(&self, token: &str) -> Option<Symbol> {
+        self.response.get(token).copied()
+    }
+
+    pub fn outcome(&self, token: &str) -> Option<Goal> {
+        self.outcome.get(token).copied()
+    }
+
+    /// A token for the `index`th value of `column`, in the same order as [`Encoding::new`]
+    ///
+    /// The first one in sort order when there are aliases. Every column has a token for each value,
+    /// since they are always given three at a time.
+    pub fn token(&self, column: Column, index: usize) -> &str {
+        match column {
+            Column::Opponent => first_token(&self.opponent, Symbol::ALL[index]),
+            Column::Response => first_token(&self.response, Symbol::ALL[index]),
+            Column::Outcome => first_token(&self.outcome, GOALS[index]),
+        }
+    }
+}
+
+fn first_token<T: PartialEq>(map: &HashMap<String, T>, value: T) -> &str {
+    map.iter()
+        .filter(|(_, known)| **known == value)
+        .map(|(token, _)| token.as_str())
+        .min()
+        .unwrap()
+}
+
+fn insert<T: Copy + PartialEq>(
+    map: &mut HashMap<String, T>,
+    column: Column,
+    tokens: [&str; 3],
+    values: [T; 3],
+) -> Result<(), String> {
+    for (token, value) in tokens.into_iter().zip(values) {
+        if map.get(token).is_some_and(|&previous| previous != value) {
+            return Err(format!("{token:?} is used twice in the {column} column"));
+        }
+
+        map.insert(token.into(), value);
+    }
+
+    Ok(())
+}
+
+/// Every column must be listed at least once
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut encoding = Self::empty();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| format!("line {}: {message}", i + 1);
+
+            let (column, tokens) = line
+                .split_once(':')
+                .ok_or_else(|| error(format!("expected `column: tokens`, got {line:?}")))?;
+            let column = column.trim().parse().map_err(error)?;
+            let tokens: [&str; 3] = tokens
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|tokens: Vec<_>| {
+                    error(format!("expected 3 tokens, got {}", tokens.len()))
+                })?;
+
+            encoding.alias(column, tokens).map_err(error)?;
+        }
+
+        for (column, empty) in [
+            ("opponent", encoding.opponent.is_empty()),
+            ("response", encoding.response.is_empty()),
+            ("outcome", encoding.outcome.is_empty()),
+        ] {
+            if empty {
+                return Err(format!("no tokens for the {column} column"));
+            }
+        }
+
+        Ok(encoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let encoding = Encoding::default();
+
+        assert_eq!(encoding.opponent("B"), Some(Symbol::Paper));
+        assert_eq!(encoding.opponent("Y"), None);
+        assert_eq!(encoding.response("Z"), Some(Symbol::Scissors));
+        assert_eq!(encoding.outcome("X"), Some(Goal::Lose));
+    }
+
+    #[test]
+    fn test_new() {
+        let encoding = Encoding::new(["R", "P", "S"], ["r", "p", "s"], ["l", "d", "w"]).unwrap();
+        assert_eq!(encoding.response("s"), Some(Symbol::Scissors));
+
+        assert_eq!(
+            Encoding::new(["R", "R", "S"], ["X", "Y", "Z"], ["X", "Y", "Z"]),
+            Err("\"R\" is used twice in the opponent column".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let encoding: Encoding = indoc::indoc! {"
+            # Words or letters
+            opponent: rock paper scissors
+            opponent: R P S
+            response: rock paper scissors
+            outcome: lose draw win
+        "}
+        .parse()
+        .unwrap();
+
+        assert_eq!(encoding.opponent("S"), Some(Symbol::Scissors));
+        assert_eq!(encoding.opponent("paper"), Some(Symbol::Paper));
+        assert_eq!(encoding.response("rock"), Some(Symbol::Rock));
+        assert_eq!(encoding.outcome("win"), Some(Goal::Win));
+
+        assert_eq!(encoding.token(Column::Opponent, 2), "S");
+        assert_eq!(encoding.token(Column::Response, 1), "paper");
+        assert_eq!(encoding.token(Column::Outcome, 0), "lose");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "opponent: A B\n".parse::<Encoding>(),
+            Err("line 1: expected 3 tokens, got 2".to_string())
+        );
+        assert_eq!(
+            "opponent: A B C\nopponent: B A C\n".parse::<Encoding>(),
+            Err("line 2: \"B\" is used twice in the opponent column".to_string())
+        );
+        assert_eq!(
+            "opponent: A B C\nresponse: X Y Z\n".parse::<Encoding>(),
+            Err("no tokens for the outcome column".to_string())
+        );
+        assert!("players: A B C\n".parse::<Encoding>().is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! Every round of the guide with how it was scored, to track down disagreeing totals
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::encoding::Encoding;
+use crate::guide;
+use crate::scoring::ScoringRules;
+use crate::{Goal, Outcome, Symbol};
+
+/// Which way the second column is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    /// What we play
+    P1,
+    /// How the round must end
+    P2,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "p1" => Ok(Self::P1),
+            "p2" => Ok(Self::P2),
+            _ => Err(format!("unknown part {s:?}, expected p1 or p2")),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::P1 => write!(f, "p1"),
+            Self::P2 => write!(f, "p2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row<'a> {
+    pub part: Part,
+    /// Starting at 1
+    pub line: usize,
+    pub raw: &'a str,
+    pub opponent: Symbol,
+    pub ours: Symbol,
+    pub outcome: Goal,
+    pub shape_points: usize,
+    pub outcome_points: usize,
+    /// Score of this round and every one before it, under the same part
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("unknown format {s:?}, expected table or csv")),
+        }
+    }
+}
+
+/// One row per round under `part`, failing at the first round that can't be read
+pub fn explain<'a>(
+    input: &'a str,
+    encoding: &Encoding,
+    rules: &ScoringRules,
+    part: Part,
+) -> Result<Vec<Row<'a>>, guide::Error> {
+    let mut total = 0;
+
+    guide::rounds(input)
+        .map(|round| {
+            let round = round?;
+            let opponent = round.opponent(encoding)?;
+            let outcome = match part {
+                Part::P1 => Outcome::from((opponent, round.response(encoding)?)),
+                Part::P2 => Outcome::from((opponent, round.outcome(encoding)?)),
+            };
+
+            let overflow = guide::Error::Overflow { line: round.line };
+            let shape_points = rules.shape_points(outcome).ok_or(overflow.clone())?;
+            let outcome_points = rules.outcome_points(outcome).ok_or(overflow.clone())?;
+            total = [shape_points, outcome_points]
+                .into_iter()
+                .try_fold(total, usize::checked_add)
+                .ok_or(overflow)?;
+
+            Ok(Row {
+                part,
+                line: round.line,
+                raw: round.raw,
+                opponent,
+                ours: outcome.symbol(),
+                outcome: outcome.goal(),
+                shape_points,
+                outcome_points,
+                total,
+            })
+        })
+        .collect()
+}
+
+/// Both parts, part 1 first
+pub fn explain_both<'a>(
+    input: &'a str,
+    encoding: &Encoding,
+    rules: &ScoringRules,
+) -> Result<Vec<Row<'a>>, guide::Error> {
+    let mut rows = explain(input, encoding, rules, Part::P1)?;
+    rows.extend(explain(input, encoding, rules, Part::P2)?);
+
+    Ok(rows)
+}
+
+pub fn render(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Table => table(rows),
+        Format::Csv => csv(rows),
+    }
+}
+
+const HEADER: [&str; 9] = [
+    "part", "line", "raw", "opponent", "ours", "outcome", "shape", "points", "total",
+];
+
+fn cells(row: &Row) -> Vec<String> {
+    vec![
+        row.part.to_string(),
+        row.line.to_string(),
+        row.raw.trim().to_string(),
+        format!("{:?}", row.opponent),
+        format!("{:?}", row.ours),
+        format!("{:?}", row.outcome),
+        row.shape_points.to_string(),
+        row.outcome_points.to_string(),
+        row.total.to_string(),
+    ]
+}
+
+fn table(rows: &[Row]) -> String {
+    let rows = rows.iter().map(cells).collect_vec();
+    let widths = HEADER
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([title.len()])
+                .max()
+                .unwrap()
+        })
+        .collect_vec();
+
+    let mut out = String::new();
+    let header = HEADER.iter().map(ToString::to_string).collect_vec();
+
+    for row in [header].iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .join("  ");
+
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+
+    out
+}
+
+fn csv(rows: &[Row]) -> String {
+    let mut out = HEADER.join(",") + "\n";
+
+    for row in rows {
+        let mut cells = cells(row);
+        // The raw line is the only cell that could hold a comma or a quote
+        cells[2] = format!("\"{}\"", row.raw.replace('"', "\"\""));
+
+        writeln!(out, "{}", cells.join(",")).unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[test]
+    fn test_explain() {
+        let encoding = Encoding::default();
+        let rows = explain_both(EXAMPLE, &encoding, &ScoringRules::PUZZLE).unwrap();
+
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[2].total, 15);
+        assert_eq!(rows[5].total, 12);
+        assert_eq!(
+            rows[4],
+            Row {
+                part: Part::P2,
+                line: 2,
+                raw: "B X",
+                opponent: Symbol::Paper,
+                ours: Symbol::Rock,
+                outcome: Goal::Lose,
+                shape_points: 1,
+                outcome_points: 0,
+                total: 5
+            }
+        );
+
+        assert_eq!(
+            explain("A Y\nA Q\n", &encoding, &ScoringRules::PUZZLE, Part::P1),
+            Err(guide::Error::UnknownToken {
+                line: 2,
+                column: crate::encoding::Column::Response,
+                token: "Q".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let rows = explain(
+            EXAMPLE,
+            &Encoding::default(),
+            &ScoringRules::PUZZLE,
+            Part::P1,
+        )
+        .unwrap();
+
+        assert_eq!(
+            render(&rows, Format::Table),
+            indoc::indoc! {"
+                part  line  raw  opponent  ours      outcome  shape  points  total
+                p1    1     A Y  Rock      Paper     Win      2      6       8
+                p1    2     B X  Paper     Rock      Lose     1      0       9
+                p1    3     C Z  Scissors  Scissors  Draw     3      3       15
+            "}
+        );
+
+        assert_eq!(
+            render(&rows[..1], Format::Csv),
+            indoc::indoc! {r#"
+                part,line,raw,opponent,ours,outcome,shape,points,total
+                p1,1,"A Y",Rock,Paper,Win,2,6,8
+            "#}
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Scores rounds straight from the bytes of the input, with the puzzle's encoding and scoring
+//!
+//! Lines other than `A X` to `C Z` (blank lines aside) go through the enum-based [`crate::p1`] and
+//! [`crate::p2`] instead, so errors are reported the same way.
+
+use crate::guide;
+
+/// Score of every round, indexed by the opponent's letter then ours, the second column being what
+/// we play
+const P1: [[u8; 3]; 3] = p1_table();
+
+/// Same as [`P1`], the second column being how the round must end
+const P2: [[u8; 3]; 3] = p2_table();
+
+const fn p1_table() -> [[u8; 3]; 3] {
+    let mut table = [[0; 3]; 3];
+    let mut theirs = 0;
+
+    while theirs < 3 {
+        let mut ours = 0;
+
+        while ours < 3 {
+            // 0 when we lose, 1 for a draw and 2 when we win
+            let goal = (ours + 4 - theirs) % 3;
+            table[theirs][ours] = (ours + 1 + 3 * goal) as u8;
+            ours += 1;
+        }
+
+        theirs += 1;
+    }
+
+    table
+}
+
+const fn p2_table() -> [[u8; 3]; 3] {
+    let mut table = [[0; 3]; 3];
+    let mut theirs = 0;
+
+    while theirs < 3 {
+        let mut goal = 0;
+
+        while goal < 3 {
+            let ours = (theirs + goal + 2) % 3;
+            table[theirs][goal] = (ours + 1 + 3 * goal) as u8;
+            goal += 1;
+        }
+
+        theirs += 1;
+    }
+
+    table
+}
+
+/// `None` as soon as a line isn't a plain round
+fn score(input: &str, table: &[[u8; 3]; 3]) -> Option<usize> {
+    let mut total = 0;
+
+    for line in input.as_bytes().split(|&b| b == b'\n') {
+        match *line {
+            [] => {}
+            [theirs @ b'A'..=b'C', b' ', second @ b'X'..=b'Z'] => {
+                total += table[(theirs - b'A') as usize][(second - b'X') as usize] as usize;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(total)
+}
+
+pub fn p1(input: &str) -> Result<usize, guide::Error> {
+    score(input, &P1).map_or_else(|| crate::p1(input), Ok)
+}
+
+pub fn p2(input: &str) -> Result<usize, guide::Error> {
+    score(input, &P2).map_or_else(|| crate::p2(input), Ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tournament::{Random, Strategy};
+    use crate::{Outcome, Symbol, EXAMPLE};
+
+    #[test]
+    fn test_tables() {
+        for (theirs, letter) in ["A", "B", "C"].into_iter().enumerate() {
+            for (second, response) in ["X", "Y", "Z"].into_iter().enumerate() {
+                let round = format!("{letter} {response}\n");
+
+                assert_eq!(Ok(P1[theirs][second] as usize), crate::p1(&round));
+                assert_eq!(Ok(P2[theirs][second] as usize), crate::p2(&round));
+            }
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_enums() {
+        let mut random = Random::new(2);
+        let mut pick = |n: usize| (0..n).map(|_| random.play(&[])).collect::<Vec<_>>();
+
+        for rounds in [0, 1, 2, 10, 100, 1000] {
+            let guide = pick(rounds)
+                .into_iter()
+                .zip(pick(rounds))
+                .map(|(theirs, second)| {
+                    let letter = |base: u8, symbol: Symbol| (base + symbol.index() as u8) as char;
+                    format!("{} {}\n", letter(b'A', theirs), letter(b'X', second))
+                })
+                .collect::<String>();
+
+            assert_eq!(p1(&guide), crate::p1(&guide), "{guide}");
+            assert_eq!(p2(&guide), crate::p2(&guide), "{guide}");
+        }
+
+        for input in [
+            EXAMPLE,
+            "A Y\n\nB X",
+            "A  Y\r\nB X\n",
+            "A Y\nD X\n",
+            "A Y\nB\n",
+        ] {
+            assert_eq!(p1(input), crate::p1(input), "{input:?}");
+            assert_eq!(p2(input), crate::p2(input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(p1(EXAMPLE), Ok(15));
+        assert_eq!(p2(EXAMPLE), Ok(12));
+        assert_eq!(
+            usize::from(Outcome::from((Symbol::Rock, Symbol::Paper))),
+            P1[0][1] as usize
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Games like rock-paper-scissors, driven by which symbol beats which
+//!
+//! Outcomes and the symbol to play for a desired outcome are derived from the dominance relation,
+//! so Rock-Paper-Scissors-Lizard-Spock or any balanced cyclic game with an odd number of symbols
+//! works the same as the puzzle's. Scores come from [`ScoringRules`] with points for each of the
+//! game's symbols.
+
+use std::sync::OnceLock;
+
+use crate::scoring::ScoringRules;
+use crate::Goal;
+
+/// Symbols are indices in `0..len()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    /// `beats[a][b]` when `a` beats `b`
+    beats: Vec<Vec<bool>>,
+}
+
+impl Game {
+    /// `beats` lists every `(winner, loser)` pair by name
+    ///
+    /// Every two symbols must have a winner, and every symbol must beat as many symbols as it
+    /// loses to, so the game is fair.
+    pub fn new(names: &[&str], beats: &[(&str, &str)]) -> Result<Self, String> {
+        let index = |name: &str| {
+            names
+                .iter()
+                .position(|&n| n == name)
+                .ok_or_else(|| format!("unknown symbol {name:?}"))
+        };
+
+        let mut game = Self {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats: vec![vec![false; names.len()]; names.len()],
+        };
+
+        for &(winner, loser) in beats {
+            let (winner, loser) = (index(winner)?, index(loser)?);
+
+            if winner == loser {
+                return Err(format!("{} can't beat itself", names[winner]));
+            }
+
+            game.beats[winner][loser] = true;
+        }
+
+        game.check()?;
+
+        Ok(game)
+    }
+
+    /// `n` symbols, each beating the `(n - 1) / 2` symbols before it, wrapping around
+    pub fn cyclic(n: usize) -> Result<Self, String> {
+        if n.is_multiple_of(2) {
+            return Err(format!(
+                "{n} symbols can't make a fair game, it needs an odd number"
+            ));
+        }
+
+        let names = (1..=n).map(|i| i.to_string()).collect::<Vec<_>>();
+        let mut beats = vec![vec![false; n]; n];
+
+        for (winner, row) in beats.iter_mut().enumerate() {
+            for distance in 1..=(n - 1) / 2 {
+                row[(winner + n - distance) % n] = true;
+            }
+        }
+
+        let game = Self { names, beats };
+        game.check()?;
+
+        Ok(game)
+    }
+
+    /// The puzzle's game, shared since it's used for every round
+    pub fn rock_paper_scissors() -> &'static Self {
+        static GAME: OnceLock<Game> = OnceLock::new();
+
+        GAME.get_or_init(|| {
+            Self::new(
+                &["Rock", "Paper", "Scissors"],
+                &[
+                    ("Rock", "Scissors"),
+                    ("Paper", "Rock"),
+                    ("Scissors", "Paper"),
+                ],
+            )
+            .unwrap()
+        })
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(
+            &["Rock", "Paper", "Scissors", "Lizard", "Spock"],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn check(&self) -> Result<(), String> {
+        let n = self.len();
+
+        if n < 3 {
+            return Err(format!("a game needs at least 3 symbols, got {n}"));
+        }
+
+        for a in 0..n {
+            for b in a + 1..n {
+                if self.beats[a][b] == self.beats[b][a] {
+                    return Err(format!(
+                        "exactly one of {} and {} must beat the other",
+                        self.names[a], self.names[b]
+                    ));
+                }
+            }
+
+            let wins = self.beats[a].iter().filter(|&&beats| beats).count();
+            if wins * 2 != n - 1 {
+                return Err(format!(
+                    "{} beats {wins} of the {} other symbols, the game isn't fair",
+                    self.names[a],
+                    n - 1
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, symbol: usize) -> &str {
+        &self.names[symbol]
+    }
+
+    pub fn beats(&self, a: usize, b: usize) -> bool {
+        self.beats[a][b]
+    }
+
+    /// How the round ends for us
+    pub fn resolve(&self, ours: usize, theirs: usize) -> Goal {
+        if self.beats(ours, theirs) {
+            Goal::Win
+        } else if self.beats(theirs, ours) {
+            Goal::Lose
+        } else {
+            Goal::Draw
+        }
+    }
+
+    /// Points for playing `ours` against `theirs`, `None` if they overflow. `rules` has points for
+    /// each of the game's symbols
+    pub fn score<const N: usize>(
+        &self,
+        ours: usize,
+        theirs: usize,
+        rules: &ScoringRules<N>,
+    ) -> Option<usize> {
+        assert_eq!(N, self.len(), "scoring rules for another number of symbols");
+
+        rules.points(ours, self.resolve(ours, theirs))
+    }
+
+    /// Every symbol ending the round with `goal` against `theirs`
+    pub fn choices(&self, theirs: usize, goal: Goal) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(move |&ours| self.resolve(ours, theirs) == goal)
+    }
+
+    /// The first of [`Game::choices`], a fair game always has one
+    pub fn choose(&self, theirs: usize, goal: Goal) -> usize {
+        self.choices(theirs, goal).next().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rock_paper_scissors() {
+        let game = Game::rock_paper_scissors();
+
+        assert_eq!(game.resolve(1, 0), Goal::Win);
+        assert_eq!(game.resolve(0, 1), Goal::Lose);
+        assert_eq!(game.resolve(2, 2), Goal::Draw);
+        assert_eq!(game.choose(0, Goal::Lose), 2);
+        assert_eq!(&Game::cyclic(3).unwrap().beats, &game.beats);
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let (rock, paper, scissors, lizard, spock) = (0, 1, 2, 3, 4);
+
+        assert_eq!(game.resolve(spock, rock), Goal::Win);
+        assert_eq!(game.resolve(lizard, scissors), Goal::Lose);
+        assert_eq!(
+            game.choices(paper, Goal::Win).collect::<Vec<_>>(),
+            vec![scissors, lizard]
+        );
+        assert_eq!(game.choose(paper, Goal::Draw), paper);
+    }
+
+    #[test]
+    fn test_score() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let (rock, paper, spock) = (0, 1, 4);
+
+        // Spock is the 5th symbol
+        let rules = ScoringRules::<5>::default();
+        assert_eq!(game.score(spock, rock, &rules), Some(5 + 6));
+        assert_eq!(game.score(rock, spock, &rules), Some(1));
+        assert_eq!(game.score(paper, paper, &rules), Some(2 + 3));
+
+        let rules: ScoringRules<5> = "shape: 1 2 3 4 5\nmultiplier: 1 1 2\n".parse().unwrap();
+        assert_eq!(game.score(spock, rock, &rules), Some(22));
+        assert_eq!(
+            "shape: 1 2 3\n".parse::<ScoringRules<5>>(),
+            Err("line 1: expected 5 values, got 3".to_string())
+        );
+
+        let puzzle = Game::rock_paper_scissors();
+        assert_eq!(puzzle.score(1, 0, &ScoringRules::PUZZLE), Some(8));
+    }
+
+    #[test]
+    #[should_panic(expected = "scoring rules for another number of symbols")]
+    fn test_score_with_other_symbols() {
+        Game::rock_paper_scissors_lizard_spock().score(0, 1, &ScoringRules::PUZZLE);
+    }
+
+    #[test]
+    fn test_every_goal_is_reachable() {
+        for n in [3, 5, 7, 9] {
+            let game = Game::cyclic(n).unwrap();
+
+            for theirs in 0..n {
+                assert_eq!(game.choices(theirs, Goal::Draw).count(), 1);
+                assert_eq!(game.choices(theirs, Goal::Win).count(), (n - 1) / 2);
+                assert_eq!(game.choices(theirs, Goal::Lose).count(), (n - 1) / 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        assert!(Game::cyclic(4).is_err());
+        assert!(Game::new(&["Rock", "Paper"], &[("Paper", "Rock")]).is_err());
+        assert_eq!(
+            Game::new(
+                &["A", "B", "C", "D", "E"],
+                &[
+                    ("A", "B"),
+                    ("A", "C"),
+                    ("A", "D"),
+                    ("A", "E"),
+                    ("B", "C"),
+                    ("C", "D"),
+                    ("D", "E"),
+                    ("E", "B"),
+                    ("B", "D"),
+                    ("C", "E"),
+                ]
+            ),
+            Err("A beats 4 of the 4 other symbols, the game isn't fair".into())
+        );
+        assert_eq!(
+            Game::new(&["A", "B", "C"], &[("A", "B"), ("B", "C")]),
+            Err("exactly one of A and C must beat the other".into())
+        );
+        assert!(Game::new(&["A"], &[("A", "A")]).is_err());
+    }
+}
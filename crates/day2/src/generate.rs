@@ -0,0 +1,498 @@
+//! Strategy guides with a known answer, for tests
+//!
+//! A round scores one of a few values. Above the lowest of them, a total is reachable in `n`
+//! rounds when the fewest rounds adding up to it is at most `n`, the others scoring the lowest.
+//! Those fewest rounds have a closed form when the scores above the lowest form an interval, and
+//! come from a table over the totals otherwise, so a target that can't be reached is proven so
+//! rather than just not found. Past a bound set by the highest score, the fewest rounds repeat
+//! with it, so the table stops there and stays small for any target. The guide is then scored the usual way to check it.
+
+use std::fmt;
+
+use crate::encoding::{Column, Encoding};
+use crate::explain::Part;
+use crate::guide::{self, BadRounds};
+use crate::scoring::ScoringRules;
+use crate::{Goal, Outcome, Symbol};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// No guide of `rounds` rounds scores `target`
+    Unreachable {
+        rounds: usize,
+        target: usize,
+        part: Part,
+        /// Lowest and highest totals `rounds` rounds can score, some between them may be
+        /// unreachable too
+        min: usize,
+        max: usize,
+    },
+    /// The totals of `rounds` rounds don't fit in a `usize`
+    Overflow { rounds: usize },
+    /// The scores of a round are too far apart, finding the fewest rounds adding up to the target
+    /// needs a table of more than [`TABLE_LIMIT`] totals
+    TooLarge { totals: usize },
+    /// The generated guide scores something else, which is a bug
+    Mismatch {
+        target: usize,
+        scored: Result<usize, guide::Error>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreachable {
+                rounds,
+                target,
+                part,
+                min,
+                max,
+            } => write!(
+                f,
+                "no guide of {rounds} rounds scores {target} under {part}, totals range from {min} to {max}"
+            ),
+            Self::Overflow { rounds } => write!(f, "the totals of {rounds} rounds overflow"),
+            Self::TooLarge { totals } => write!(
+                f,
+                "the scores of a round are too far apart, generating needs a table of {totals} totals, more than {TABLE_LIMIT}"
+            ),
+            Self::Mismatch {
+                target,
+                scored: Ok(score),
+            } => write!(f, "generated a guide scoring {score} instead of {target}"),
+            Self::Mismatch {
+                scored: Err(err), ..
+            } => write!(f, "generated a guide that can't be scored: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+const GOALS: [Goal; 3] = [Goal::Lose, Goal::Draw, Goal::Win];
+
+/// Every score a round can have, lowest first, each with a line of `encoding` scoring it
+fn lines(part: Part, encoding: &Encoding, rules: &ScoringRules) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let column = match part {
+        Part::P1 => Column::Response,
+        Part::P2 => Column::Outcome,
+    };
+
+    for theirs in Symbol::ALL {
+        for (second, goal) in GOALS.into_iter().enumerate() {
+            let outcome = match part {
+                Part::P1 => Outcome::from((theirs, Symbol::from_index(second))),
+                Part::P2 => Outcome::from((theirs, goal)),
+            };
+            // Rounds whose points overflow can't be part of any guide
+            let Some(score) = rules.score(outcome) else {
+                continue;
+            };
+
+            if lines.iter().all(|&(known, _)| known != score) {
+                let line = format!(
+                    "{} {}",
+                    encoding.token(Column::Opponent, theirs.index()),
+                    encoding.token(column, second)
+                );
+                lines.push((score, line));
+            }
+        }
+    }
+
+    lines.sort();
+    lines
+}
+
+/// Most totals [`Fewest`] keeps a table of
+pub const TABLE_LIMIT: usize = 1 << 24;
+
+/// Fewest steps above zero adding up to each total, from distinct steps sorted from zero
+enum Fewest {
+    /// The steps are every number up to this one
+    Interval(usize),
+    /// Among `largest` steps smaller than the largest, some always add up to a multiple of it
+    /// and are better replaced by fewer of it. So the fewest steps use at most `largest - 1`
+    /// smaller ones, and past `(largest - 1)²` only differ by how many of the largest they use
+    Table {
+        largest: usize,
+        /// By total, `usize::MAX` when no steps add up to it
+        table: Vec<usize>,
+        /// By total modulo `largest`, the fewest steps of the tabled totals minus how many times
+        /// `largest` goes into them
+        by_residue: Vec<usize>,
+    },
+}
+
+impl Fewest {
+    /// Knows about totals up to `up_to`
+    fn new(steps: &[usize], up_to: usize) -> Result<Self, Error> {
+        let largest = *steps.last().unwrap();
+
+        if largest > 0 && steps.len() == largest + 1 {
+            return Ok(Self::Interval(largest));
+        }
+
+        let smaller = largest.saturating_sub(1);
+        let periodic = smaller.saturating_mul(smaller);
+        let totals = up_to.min(periodic).saturating_add(1);
+        if totals > TABLE_LIMIT {
+            return Err(Error::TooLarge { totals });
+        }
+
+        let mut table = vec![usize::MAX; totals];
+        table[0] = 0;
+
+        for total in 1..totals {
+            table[total] = steps
+                .iter()
+                .filter(|&&step| (1..=total).contains(&step))
+                .map(|&step| table[total - step])
+                .min()
+                .unwrap_or(usize::MAX)
+                .saturating_add(1);
+        }
+
+        // Every round scoring the same leaves nothing past the table
+        let mut by_residue = vec![usize::MAX; largest];
+        if largest > 0 {
+            for (total, &fewest) in table.iter().enumerate() {
+                let residue = &mut by_residue[total % largest];
+                *residue = (*residue).min(fewest.saturating_sub(total / largest));
+            }
+        }
+
+        Ok(Self::Table {
+            largest,
+            table,
+            by_residue,
+        })
+    }
+
+    fn get(&self, total: usize) -> usize {
+        match self {
+            Self::Interval(largest) => total.div_ceil(*largest),
+            Self::Table { table, .. } if total < table.len() => table[total],
+            Self::Table {
+                largest,
+                by_residue,
+                ..
+            } => (total / largest).saturating_add(by_residue[total % largest]),
+        }
+    }
+}
+
+fn gcd_of(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        b => gcd_of(b, a % b),
+    }
+}
+
+/// A guide of `rounds` rounds in `encoding` scoring exactly `target` under `part`
+pub fn generate(
+    rounds: usize,
+    target: usize,
+    part: Part,
+    encoding: &Encoding,
+    rules: &ScoringRules,
+) -> Result<String, Error> {
+    let lines = lines(part, encoding, rules);
+    let (Some(&(lowest, _)), Some(&(highest, _))) = (lines.first(), lines.last()) else {
+        return Err(Error::Overflow { rounds });
+    };
+
+    let min = rounds
+        .checked_mul(lowest)
+        .ok_or(Error::Overflow { rounds })?;
+    let max = rounds
+        .checked_mul(highest)
+        .ok_or(Error::Overflow { rounds })?;
+    let unreachable = Error::Unreachable {
+        rounds,
+        target,
+        part,
+        min,
+        max,
+    };
+
+    if !(min..=max).contains(&target) {
+        return Err(unreachable);
+    }
+
+    // Every round scores at least the lowest, so only what's above it is left to spread, in
+    // multiples of the greatest common divisor of the scores above the lowest
+    let divisor = lines
+        .iter()
+        .fold(0, |gcd, &(score, _)| gcd_of(gcd, score - lowest))
+        .max(1);
+
+    if !(target - min).is_multiple_of(divisor) {
+        return Err(unreachable);
+    }
+
+    let steps: Vec<usize> = lines
+        .iter()
+        .map(|&(score, _)| (score - lowest) / divisor)
+        .collect();
+    let mut left = (target - min) / divisor;
+    let fewest = Fewest::new(&steps, left)?;
+
+    if fewest.get(left) > rounds {
+        return Err(unreachable);
+    }
+
+    let mut guide = String::new();
+
+    for k in (1..=rounds).rev() {
+        // The first step of the fewest adding up to `left` always fits, or no step at all when
+        // fewer than `k` do
+        let (step, (_, line)) = steps
+            .iter()
+            .zip(&lines)
+            .rev()
+            .find(|&(&step, _)| step <= left && fewest.get(left - step) < k)
+            .unwrap();
+
+        guide += line;
+        guide += "\n";
+        left -= step;
+    }
+
+    let scored = match part {
+        Part::P1 => crate::p1_with(&guide, encoding, BadRounds::Fail, rules),
+        Part::P2 => crate::p2_with(&guide, encoding, BadRounds::Fail, rules),
+    }
+    .map(|scored| scored.score);
+
+    if scored != Ok(target) {
+        return Err(Error::Mismatch { target, scored });
+    }
+
+    Ok(guide)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_puzzle_rules() {
+        let encoding = Encoding::default();
+
+        // Rounds score anything from 1 to 9 either way
+        for part in [Part::P1, Part::P2] {
+            for rounds in 0..5 {
+                for target in 0..50 {
+                    let guide = generate(rounds, target, part, &encoding, &ScoringRules::PUZZLE);
+
+                    assert_eq!(
+                        guide.is_ok(),
+                        (rounds..=9 * rounds).contains(&target),
+                        "{rounds} rounds, {target}"
+                    );
+                    if let Ok(guide) = guide {
+                        assert_eq!(guide.lines().count(), rounds);
+                    }
+                }
+            }
+        }
+
+        let guide = generate(2500, 12535, Part::P1, &encoding, &ScoringRules::PUZZLE).unwrap();
+        assert_eq!(crate::p1(&guide), Ok(12535));
+
+        // Nothing is kept per round
+        let guide = generate(100_000, 500_001, Part::P2, &encoding, &ScoringRules::PUZZLE);
+        assert_eq!(guide.map(|guide| guide.lines().count()), Ok(100_000));
+    }
+
+    #[test]
+    fn test_gaps() {
+        let encoding = Encoding::default();
+        // Only outcomes count, so every total is a multiple of 3
+        let rules = ScoringRules {
+            outcome: [0, 3, 6],
+            shape: [0, 0, 0],
+            multipliers: None,
+        };
+
+        assert_eq!(
+            generate(2, 9, Part::P2, &encoding, &rules),
+            Ok("A Z\nA Y\n".to_string())
+        );
+        assert_eq!(
+            generate(2, 4, Part::P2, &encoding, &rules),
+            Err(Error::Unreachable {
+                rounds: 2,
+                target: 4,
+                part: Part::P2,
+                min: 0,
+                max: 12
+            })
+        );
+        assert_eq!(
+            generate(2, 4, Part::P1, &encoding, &rules)
+                .unwrap_err()
+                .to_string(),
+            "no guide of 2 rounds scores 4 under p1, totals range from 0 to 12"
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        // Wins count double: 1 to 6, then 14, 16 and 18
+        let rules = ScoringRules {
+            multipliers: Some([1, 1, 2]),
+            ..ScoringRules::PUZZLE
+        };
+        let mut reachable = HashSet::from([0]);
+
+        for rounds in 0..5 {
+            for target in 0..80 {
+                let guide = generate(rounds, target, Part::P1, &Encoding::default(), &rules);
+
+                assert_eq!(
+                    guide.is_ok(),
+                    reachable.contains(&target),
+                    "{rounds} rounds, {target}"
+                );
+            }
+
+            reachable = reachable
+                .iter()
+                .flat_map(|total| [1, 2, 3, 4, 5, 6, 14, 16, 18].map(|score| total + score))
+                .collect();
+        }
+    }
+
+    #[test]
+    fn test_large_target() {
+        let rules = ScoringRules {
+            multipliers: Some([1, 1, 2]),
+            ..ScoringRules::PUZZLE
+        };
+        let encoding = Encoding::default();
+        let rounds = 1_000_000;
+
+        // Way past the table, which stops at (17 - 1)² totals above the lowest
+        let guide = generate(rounds, 15_000_001, Part::P1, &encoding, &rules).unwrap();
+        assert_eq!(guide.lines().count(), rounds);
+
+        // Below a perfect score, rounds fall short by 2, 4 or 12 and more, so never by 1
+        let max = 18 * rounds;
+        assert!(generate(rounds, max, Part::P1, &encoding, &rules).is_ok());
+        assert!(generate(rounds, max - 2, Part::P1, &encoding, &rules).is_ok());
+        assert_eq!(
+            generate(rounds, max - 1, Part::P1, &encoding, &rules),
+            Err(Error::Unreachable {
+                rounds,
+                target: max - 1,
+                part: Part::P1,
+                min: rounds,
+                max,
+            })
+        );
+    }
+
+    #[test]
+    fn test_periodic() {
+        for steps in [
+            &[0, 1, 2, 3, 4, 5, 13, 15, 17][..],
+            &[0, 3, 5],
+            &[0, 4, 6, 9],
+        ] {
+            let fewest = Fewest::new(steps, 2000).unwrap();
+            let mut table = vec![usize::MAX; 2001];
+            table[0] = 0;
+
+            for total in 1..=2000 {
+                for &step in &steps[1..] {
+                    if step <= total {
+                        table[total] = table[total].min(table[total - step].saturating_add(1));
+                    }
+                }
+
+                assert_eq!(fewest.get(total), table[total], "{steps:?}, {total}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_scores() {
+        let rules = ScoringRules {
+            outcome: [0, 0, 0],
+            shape: [2, 2, 2],
+            multipliers: None,
+        };
+
+        assert_eq!(
+            generate(3, 6, Part::P2, &Encoding::default(), &rules).map(|guide| guide.len()),
+            Ok(12)
+        );
+        assert!(generate(3, 7, Part::P2, &Encoding::default(), &rules).is_err());
+    }
+
+    #[test]
+    fn test_too_large() {
+        // Rounds score 0, 1, 2^20 or 2^20 + 1, the table would stop at 2^40 totals
+        let rules = ScoringRules {
+            outcome: [0, 0, 1 << 20],
+            shape: [0, 0, 1],
+            multipliers: None,
+        };
+        let guide = generate(2000, 1 << 30, Part::P1, &Encoding::default(), &rules);
+
+        assert_eq!(
+            guide,
+            Err(Error::TooLarge {
+                totals: (1 << 30) + 1
+            })
+        );
+        assert_eq!(
+            guide.unwrap_err().to_string(),
+            format!("the scores of a round are too far apart, generating needs a table of {} totals, more than {TABLE_LIMIT}", (1 << 30) + 1)
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        let encoding: Encoding = indoc::indoc! {"
+            opponent: rock paper scissors
+            response: R P S
+            outcome: lose draw win
+        "}
+        .parse()
+        .unwrap();
+        let rules = ScoringRules::PUZZLE;
+
+        assert_eq!(
+            generate(2, 18, Part::P1, &encoding, &rules),
+            Ok("paper S\npaper S\n".to_string())
+        );
+        assert_eq!(
+            generate(1, 1, Part::P2, &encoding, &rules),
+            Ok("paper lose\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let guide = generate(
+            usize::MAX,
+            5,
+            Part::P1,
+            &Encoding::default(),
+            &ScoringRules::PUZZLE,
+        );
+
+        assert_eq!(guide, Err(Error::Overflow { rounds: usize::MAX }));
+        assert_eq!(
+            guide.unwrap_err().to_string(),
+            format!("the totals of {} rounds overflow", usize::MAX)
+        );
+    }
+}
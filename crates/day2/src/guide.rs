@@ -0,0 +1,226 @@
+//! Rounds of a strategy guide, and what goes wrong reading them
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::encoding::{Column, Encoding};
+use crate::scoring::ScoringRules;
+use crate::{Goal, Outcome, Symbol};
+
+/// One line of the guide, not decoded yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round<'a> {
+    /// Starting at 1
+    pub line: usize,
+    pub raw: &'a str,
+    pub first: &'a str,
+    pub second: &'a str,
+}
+
+impl Round<'_> {
+    pub fn opponent(&self, encoding: &Encoding) -> Result<Symbol, Error> {
+        encoding
+            .opponent(self.first)
+            .ok_or_else(|| self.unknown(Column::Opponent, self.first))
+    }
+
+    pub fn response(&self, encoding: &Encoding) -> Result<Symbol, Error> {
+        encoding
+            .response(self.second)
+            .ok_or_else(|| self.unknown(Column::Response, self.second))
+    }
+
+    pub fn outcome(&self, encoding: &Encoding) -> Result<Goal, Error> {
+        encoding
+            .outcome(self.second)
+            .ok_or_else(|| self.unknown(Column::Outcome, self.second))
+    }
+
+    /// Points for playing the round as `outcome`
+    pub fn points(&self, rules: &ScoringRules, outcome: Outcome) -> Result<usize, Error> {
+        rules
+            .score(outcome)
+            .ok_or(Error::Overflow { line: self.line })
+    }
+
+    fn unknown(&self, column: Column, token: &str) -> Error {
+        Error::UnknownToken {
+            line: self.line,
+            column,
+            token: token.to_string(),
+        }
+    }
+}
+
+/// Every line of the guide but blank ones, each with exactly two tokens
+pub fn rounds(input: &str) -> impl Iterator<Item = Result<Round<'_>, Error>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, raw)| !raw.trim().is_empty())
+        .map(|(i, raw)| {
+            let mut tokens = raw.split_whitespace();
+
+            match (tokens.next(), tokens.next(), tokens.next()) {
+                (Some(first), Some(second), None) => Ok(Round {
+                    line: i + 1,
+                    raw,
+                    first,
+                    second,
+                }),
+                _ => Err(Error::Tokens {
+                    line: i + 1,
+                    tokens: raw.split_whitespace().count(),
+                }),
+            }
+        })
+}
+
+/// Lines start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The line doesn't have exactly two tokens
+    Tokens { line: usize, tokens: usize },
+    UnknownToken {
+        line: usize,
+        column: Column,
+        token: String,
+    },
+    /// The score overflows at this line
+    Overflow { line: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tokens { line, tokens } => {
+                write!(f, "line {line}: expected 2 tokens, got {tokens}")
+            }
+            Self::UnknownToken {
+                line,
+                column,
+                token,
+            } => write!(f, "line {line}: {token:?} is not a known {column} token"),
+            Self::Overflow { line } => write!(f, "line {line}: the score overflows"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// What to do with rounds that can't be read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BadRounds {
+    /// Stop at the first one
+    #[default]
+    Fail,
+    Skip,
+    /// Skip them, keeping their errors in [`Scored::errors`]
+    Collect,
+}
+
+impl FromStr for BadRounds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fail" => Ok(Self::Fail),
+            "skip" => Ok(Self::Skip),
+            "collect" => Ok(Self::Collect),
+            _ => Err(format!(
+                "unknown policy {s:?}, expected fail, skip or collect"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scored {
+    pub score: usize,
+    /// Rounds left out of the score, with [`BadRounds::Collect`]
+    pub errors: Vec<Error>,
+}
+
+/// Sums the score of every round, `score` reading one
+///
+/// A total overflowing fails whatever `bad_rounds` is, since skipping rounds would change it.
+pub fn score<'a>(
+    input: &'a str,
+    bad_rounds: BadRounds,
+    score: impl Fn(&Round<'a>) -> Result<usize, Error>,
+) -> Result<Scored, Error> {
+    let mut scored = Scored::default();
+
+    for round in rounds(input) {
+        match round.and_then(|round| Ok((round.line, score(&round)?))) {
+            Ok((line, points)) => {
+                scored.score = scored
+                    .score
+                    .checked_add(points)
+                    .ok_or(Error::Overflow { line })?;
+            }
+            Err(err) => match bad_rounds {
+                BadRounds::Fail => return Err(err),
+                BadRounds::Skip => {}
+                BadRounds::Collect => scored.errors.push(err),
+            },
+        }
+    }
+
+    Ok(scored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds() {
+        let rounds: Vec<_> = rounds("A Y\n\n B\tX \nC\nA B C\n").collect();
+
+        assert_eq!(
+            rounds,
+            vec![
+                Ok(Round {
+                    line: 1,
+                    raw: "A Y",
+                    first: "A",
+                    second: "Y"
+                }),
+                Ok(Round {
+                    line: 3,
+                    raw: " B\tX ",
+                    first: "B",
+                    second: "X"
+                }),
+                Err(Error::Tokens { line: 4, tokens: 1 }),
+                Err(Error::Tokens { line: 5, tokens: 3 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let points = |round: &Round| Ok(if round.first == "A" { usize::MAX } else { 1 });
+
+        assert_eq!(
+            score("A Y\nB Y\n", BadRounds::Skip, points),
+            Err(Error::Overflow { line: 2 })
+        );
+        assert_eq!(
+            score("A Y\n", BadRounds::Fail, points).map(|scored| scored.score),
+            Ok(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let round = rounds("A Y\nQ X\n").nth(1).unwrap().unwrap();
+        let err = round.opponent(&Encoding::default()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "line 2: \"Q\" is not a known opponent token"
+        );
+    }
+}
@@ -0,0 +1,473 @@
+//! # Types
+//!
+//! Rock     A, X, 1
+//! Paper    B, Y, 2
+//! Scissors C, Z, 3
+//!
+//! # Scoring
+//!
+//! Score = Move + Outcome
+//!
+//! Outcome
+//!   Loss = 0
+//!   Draw = 3
+//!   Win  = 6
+//!
+//! Variants use other points, see [`scoring::ScoringRules`]
+
+pub mod best;
+pub mod encoding;
+pub mod explain;
+pub mod fast;
+pub mod game;
+pub mod generate;
+pub mod guide;
+pub mod predict;
+pub mod scoring;
+pub mod tournament;
+
+use std::fmt;
+
+use encoding::Encoding;
+use game::Game;
+use guide::{BadRounds, Scored};
+use scoring::ScoringRules;
+
+/// Used when the real input isn't available
+pub const EXAMPLE: &str = indoc::indoc! {"
+    A Y
+    B X
+    C Z
+"};
+
+/// Part of the key of cached answers, changes with the code of the crate
+pub const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("AOC_SOURCE_HASH"));
+
+pub fn p1(input: &str) -> Result<usize, guide::Error> {
+    p1_with(
+        input,
+        Encoding::puzzle(),
+        BadRounds::Fail,
+        &ScoringRules::PUZZLE,
+    )
+    .map(|scored| scored.score)
+}
+
+pub fn p2(input: &str) -> Result<usize, guide::Error> {
+    p2_with(
+        input,
+        Encoding::puzzle(),
+        BadRounds::Fail,
+        &ScoringRules::PUZZLE,
+    )
+    .map(|scored| scored.score)
+}
+
+/// Second column is what we play
+pub fn p1_with(
+    input: &str,
+    encoding: &Encoding,
+    bad_rounds: BadRounds,
+    rules: &ScoringRules,
+) -> Result<Scored, guide::Error> {
+    guide::score(input, bad_rounds, |round| {
+        let opponent = round.opponent(encoding)?;
+        let response = round.response(encoding)?;
+
+        round.points(rules, Outcome::from((opponent, response)))
+    })
+}
+
+/// Second column is how the round must end
+pub fn p2_with(
+    input: &str,
+    encoding: &Encoding,
+    bad_rounds: BadRounds,
+    rules: &ScoringRules,
+) -> Result<Scored, guide::Error> {
+    guide::score(input, bad_rounds, |round| {
+        let opponent = round.opponent(encoding)?;
+        let goal = round.outcome(encoding)?;
+
+        round.points(rules, Outcome::from((opponent, goal)))
+    })
+}
+
+/// A token the puzzle's encoding doesn't know
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownToken(pub String);
+
+impl fmt::Display for UnknownToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown token {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownToken {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Symbol {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl Symbol {
+    pub const ALL: [Self; 3] = [Self::Rock, Self::Paper, Self::Scissors];
+
+    /// Position in [`Game::rock_paper_scissors`]
+    pub fn index(self) -> usize {
+        self as usize - 1
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            0 => Self::Rock,
+            1 => Self::Paper,
+            2 => Self::Scissors,
+            _ => panic!("No symbol at {index}"),
+        }
+    }
+}
+
+/// Either column of the puzzle's encoding
+impl TryFrom<&str> for Symbol {
+    type Error = UnknownToken;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let encoding = Encoding::puzzle();
+
+        encoding
+            .opponent(value)
+            .or_else(|| encoding.response(value))
+            .ok_or_else(|| UnknownToken(value.to_string()))
+    }
+}
+
+/// How a round must end, the second column under the part 2 interpretation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Goal {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+    Loss { symbol: Symbol },
+    Draw { symbol: Symbol },
+    Win { symbol: Symbol },
+}
+
+/// The opponent's symbol and how the round must end, in the puzzle's encoding
+impl TryFrom<(&str, &str)> for Outcome {
+    type Error = UnknownToken;
+
+    fn try_from(value: (&str, &str)) -> Result<Self, Self::Error> {
+        let encoding = Encoding::puzzle();
+
+        let opponent_symbol = encoding
+            .opponent(value.0)
+            .ok_or_else(|| UnknownToken(value.0.to_string()))?;
+        let desired_outcome = encoding
+            .outcome(value.1)
+            .ok_or_else(|| UnknownToken(value.1.to_string()))?;
+
+        Ok(Outcome::from((opponent_symbol, desired_outcome)))
+    }
+}
+
+/// What to play against the first symbol to reach the goal
+impl From<(Symbol, Goal)> for Outcome {
+    fn from(value: (Symbol, Goal)) -> Self {
+        let (opponent, goal) = value;
+        let symbol = Symbol::from_index(Game::rock_paper_scissors().choose(opponent.index(), goal));
+
+        Outcome::new(goal, symbol)
+    }
+}
+
+/// The opponent's symbol, then ours
+impl From<(Symbol, Symbol)> for Outcome {
+    fn from(value: (Symbol, Symbol)) -> Self {
+        let (opponent, symbol) = value;
+        let goal = Game::rock_paper_scissors().resolve(symbol.index(), opponent.index());
+
+        Outcome::new(goal, symbol)
+    }
+}
+
+impl Outcome {
+    pub fn new(goal: Goal, symbol: Symbol) -> Self {
+        match goal {
+            Goal::Lose => Self::Loss { symbol },
+            Goal::Draw => Self::Draw { symbol },
+            Goal::Win => Self::Win { symbol },
+        }
+    }
+
+    /// What we played
+    pub fn symbol(self) -> Symbol {
+        match self {
+            Self::Loss { symbol } | Self::Draw { symbol } | Self::Win { symbol } => symbol,
+        }
+    }
+
+    /// How the round ended for us
+    pub fn goal(self) -> Goal {
+        match self {
+            Self::Loss { .. } => Goal::Lose,
+            Self::Draw { .. } => Goal::Draw,
+            Self::Win { .. } => Goal::Win,
+        }
+    }
+}
+
+/// The puzzle's scoring, [`ScoringRules::PUZZLE`], whose points never overflow
+impl From<Outcome> for usize {
+    fn from(value: Outcome) -> Self {
+        ScoringRules::PUZZLE.score(value).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_symbols() {
+        assert_eq!(Symbol::try_from("A").unwrap(), Symbol::Rock);
+        assert_eq!(Symbol::try_from("X").unwrap(), Symbol::Rock);
+        assert_eq!(Symbol::try_from("B").unwrap(), Symbol::Paper);
+        assert_eq!(Symbol::try_from("Y").unwrap(), Symbol::Paper);
+        assert_eq!(Symbol::try_from("C").unwrap(), Symbol::Scissors);
+        assert_eq!(Symbol::try_from("Z").unwrap(), Symbol::Scissors);
+
+        assert_eq!(Symbol::Rock as usize, 1);
+        assert_eq!(Symbol::Paper as usize, 2);
+        assert_eq!(Symbol::Scissors as usize, 3);
+    }
+
+    #[test]
+    fn test_outcomes() {
+        let games = [
+            (
+                ("A", "X"),
+                Outcome::Loss {
+                    symbol: Symbol::Scissors,
+                },
+            ),
+            (
+                ("A", "Y"),
+                Outcome::Draw {
+                    symbol: Symbol::Rock,
+                },
+            ),
+            (
+                ("A", "Z"),
+                Outcome::Win {
+                    symbol: Symbol::Paper,
+                },
+            ),
+            (
+                ("B", "X"),
+                Outcome::Loss {
+                    symbol: Symbol::Rock,
+                },
+            ),
+            (
+                ("B", "Y"),
+                Outcome::Draw {
+                    symbol: Symbol::Paper,
+                },
+            ),
+            (
+                ("B", "Z"),
+                Outcome::Win {
+                    symbol: Symbol::Scissors,
+                },
+            ),
+            (
+                ("C", "X"),
+                Outcome::Loss {
+                    symbol: Symbol::Paper,
+                },
+            ),
+            (
+                ("C", "Y"),
+                Outcome::Draw {
+                    symbol: Symbol::Scissors,
+                },
+            ),
+            (
+                ("C", "Z"),
+                Outcome::Win {
+                    symbol: Symbol::Rock,
+                },
+            ),
+        ];
+
+        for game in games {
+            assert_eq!(Outcome::try_from(game.0), Ok(game.1));
+        }
+    }
+
+    #[test]
+    fn test_outcome_value() {
+        let outcomes = [
+            (
+                Outcome::Draw {
+                    symbol: Symbol::Scissors,
+                },
+                3 + 3,
+            ),
+            (
+                Outcome::Loss {
+                    symbol: Symbol::Rock,
+                },
+                1,
+            ),
+            (
+                Outcome::Win {
+                    symbol: Symbol::Paper,
+                },
+                6 + 2,
+            ),
+        ];
+
+        for outcome in outcomes {
+            assert_eq!(<usize>::from(outcome.0), outcome.1);
+        }
+    }
+
+    #[test]
+    fn test_custom_encoding() {
+        let encoding: Encoding = indoc::indoc! {"
+            opponent: rock paper scissors
+            response: R P S
+            outcome: lose draw win
+        "}
+        .parse()
+        .unwrap();
+        let rules = ScoringRules::PUZZLE;
+
+        let p1 = p1_with(
+            "rock P\npaper R\nscissors S\n",
+            &encoding,
+            BadRounds::Fail,
+            &rules,
+        );
+        let p2 = p2_with(
+            "rock draw\npaper lose\nscissors win\n",
+            &encoding,
+            BadRounds::Fail,
+            &rules,
+        );
+
+        assert_eq!(p1.unwrap().score, 15);
+        assert_eq!(p2.unwrap().score, 12);
+    }
+
+    #[test]
+    fn test_unknown_tokens() {
+        assert_eq!(Symbol::try_from("D"), Err(UnknownToken("D".into())));
+        assert_eq!(Outcome::try_from(("A", "W")), Err(UnknownToken("W".into())));
+    }
+
+    #[test]
+    fn test_bad_rounds() {
+        let input = "A Y\nA Q\nB\nC Z\n";
+        let encoding = Encoding::default();
+        let rules = ScoringRules::PUZZLE;
+
+        assert_eq!(
+            p1(input),
+            Err(guide::Error::UnknownToken {
+                line: 2,
+                column: encoding::Column::Response,
+                token: "Q".into()
+            })
+        );
+        assert_eq!(
+            p1_with(input, &encoding, BadRounds::Skip, &rules),
+            Ok(Scored {
+                score: 8 + 6,
+                errors: vec![]
+            })
+        );
+
+        let scored = p2_with(input, &encoding, BadRounds::Collect, &rules).unwrap();
+        assert_eq!(scored.score, 4 + 7);
+        assert_eq!(
+            scored
+                .errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "line 2: \"Q\" is not a known outcome token",
+                "line 3: expected 2 tokens, got 1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scoring_rules() {
+        // Only outcomes count, and wins count double: a win, a loss and a draw both ways
+        let rules = ScoringRules {
+            outcome: [0, 1, 2],
+            shape: [0, 0, 0],
+            multipliers: Some([1, 1, 2]),
+        };
+        let encoding = Encoding::default();
+
+        assert_eq!(
+            p1_with(EXAMPLE, &encoding, BadRounds::Fail, &rules).map(|s| s.score),
+            Ok(5)
+        );
+        assert_eq!(
+            p2_with(EXAMPLE, &encoding, BadRounds::Fail, &rules).map(|s| s.score),
+            Ok(5)
+        );
+
+        // Losing with Scissors is worth everything, so the second time overflows
+        let rules = ScoringRules {
+            outcome: [usize::MAX - 3, 3, 6],
+            ..ScoringRules::PUZZLE
+        };
+        assert_eq!(
+            p2_with("A X\nA X\n", &encoding, BadRounds::Skip, &rules),
+            Err(guide::Error::Overflow { line: 2 })
+        );
+    }
+
+    mod p1 {
+        use crate::p1;
+
+        #[test]
+        fn test_example() {
+            let input = indoc::indoc! {"
+                A Y
+                B X
+                C Z
+            "};
+
+            assert_eq!(p1(input), Ok(15));
+        }
+    }
+
+    mod p2 {
+        use crate::p2;
+
+        #[test]
+        fn test_example() {
+            let input = indoc::indoc! {"
+                A Y
+                B X
+                C Z
+            "};
+
+            assert_eq!(p2(input), Ok(12));
+        }
+    }
+}
@@ -1,270 +1,140 @@
-use color_eyre::Result;
-
-/// # Types
-///
-/// Rock     A, X, 1
-/// Paper    B, Y, 2
-/// Scissors C, Z, 3
-///
-/// # Scoring
-///
-/// Score = Move + Outcome
-///
-/// Outcome
-///   Loss = 0
-///   Draw = 3
-///   Win  = 6
-pub fn main() -> Result<()> {
-    color_eyre::install()?;
-
-    let input = include_str!("input.txt");
-
-    let a1 = p1(input);
-    println!("{a1:?}");
-
-    let a2 = p2(input);
-    println!("{a2:?}");
-
-    Ok(())
-}
-
-fn p1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| line.split_once(' ').unwrap())
-        .map(|moves| (Symbol::from(moves.0), Symbol::from(moves.1)))
-        .map(Outcome::from)
-        .map(<usize>::from)
-        .sum()
-}
-
-fn p2(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| line.split_once(' ').unwrap())
-        .map(Outcome::from)
-        .map(<usize>::from)
-        .sum()
-}
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Symbol {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
-}
+use std::path::PathBuf;
 
-impl From<&str> for Symbol {
-    fn from(value: &str) -> Self {
-        match value {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissors,
-            _ => unreachable!(),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Outcome {
-    Loss { symbol: Symbol },
-    Draw { symbol: Symbol },
-    Win { symbol: Symbol },
+use clap::Parser;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use day2::best;
+use day2::encoding::Encoding;
+use day2::explain::{self, Part};
+use day2::generate;
+use day2::guide::{BadRounds, Scored};
+use day2::predict::{self, Markov};
+use day2::scoring::ScoringRules;
+use day2::tournament::{self, Strategy};
+use day2::{p1_with, p2_with, Symbol};
+
+#[derive(Debug, Parser)]
+struct Cli {
+    /// File mapping the tokens of the guide to symbols and outcomes, the puzzle's by default
+    #[arg(long)]
+    encoding: Option<PathBuf>,
+
+    /// File with the points of each outcome and shape, the puzzle's by default
+    #[arg(long)]
+    rules: Option<PathBuf>,
+
+    /// What to do with rounds that can't be read: fail, skip or collect (skip and list them)
+    #[arg(long, default_value = "fail")]
+    bad_rounds: BadRounds,
+
+    /// Compare the best responses to the opponent's column with both readings of the guide
+    #[arg(long)]
+    best: bool,
+
+    /// Show how every round is scored under both parts, as a table or csv
+    #[arg(long, value_name = "FORMAT")]
+    explain: Option<explain::Format>,
+
+    /// Play a round-robin tournament of built-in strategies and the guide, ROUNDS per match
+    #[arg(long, value_name = "ROUNDS")]
+    tournament: Option<usize>,
+
+    /// Counter the opponent's column with an order-ORDER predictor and compare it with the guide
+    #[arg(long, value_name = "ORDER")]
+    predict: Option<usize>,
+
+    /// Print a guide of ROUNDS rounds scoring exactly --target under --part, instead of solving
+    #[arg(long, value_name = "ROUNDS", requires = "target")]
+    generate: Option<usize>,
+
+    /// Score of the guide printed by --generate
+    #[arg(long, value_name = "SCORE", requires = "generate")]
+    target: Option<usize>,
+
+    /// Which way the generated guide is read: p1 or p2
+    #[arg(long, default_value = "p1")]
+    part: Part,
 }
 
-impl From<(&str, &str)> for Outcome {
-    fn from(value: (&str, &str)) -> Self {
-        let opponent_symbol = Symbol::from(value.0);
-        let desired_outcome = value.1;
-
-        match (opponent_symbol, desired_outcome) {
-            (symbol, "Y") => Outcome::Draw { symbol },
-            (Symbol::Rock, "X") => Outcome::Loss {
-                symbol: Symbol::Scissors,
-            },
-            (Symbol::Rock, "Z") => Outcome::Win {
-                symbol: Symbol::Paper,
-            },
-            (Symbol::Paper, "X") => Outcome::Loss {
-                symbol: Symbol::Rock,
-            },
-            (Symbol::Paper, "Z") => Outcome::Win {
-                symbol: Symbol::Scissors,
-            },
-            (Symbol::Scissors, "X") => Outcome::Loss {
-                symbol: Symbol::Paper,
-            },
-            (Symbol::Scissors, "Z") => Outcome::Win {
-                symbol: Symbol::Rock,
-            },
-            wat => panic!("Weird combo: {:?}", wat),
-        }
+fn report(scored: Scored) -> usize {
+    for err in &scored.errors {
+        eprintln!("skipped {err}");
     }
-}
 
-impl From<(Symbol, Symbol)> for Outcome {
-    fn from(value: (Symbol, Symbol)) -> Self {
-        match value {
-            (Symbol::Rock, symbol @ Symbol::Rock) => Self::Draw { symbol },
-            (Symbol::Rock, symbol @ Symbol::Paper) => Self::Win { symbol },
-            (Symbol::Rock, symbol @ Symbol::Scissors) => Self::Loss { symbol },
-            (Symbol::Paper, symbol @ Symbol::Rock) => Self::Loss { symbol },
-            (Symbol::Paper, symbol @ Symbol::Paper) => Self::Draw { symbol },
-            (Symbol::Paper, symbol @ Symbol::Scissors) => Self::Win { symbol },
-            (Symbol::Scissors, symbol @ Symbol::Rock) => Self::Win { symbol },
-            (Symbol::Scissors, symbol @ Symbol::Paper) => Self::Loss { symbol },
-            (Symbol::Scissors, symbol @ Symbol::Scissors) => Self::Draw { symbol },
-        }
-    }
+    scored.score
 }
 
-impl From<Outcome> for usize {
-    fn from(value: Outcome) -> Self {
-        match value {
-            Outcome::Loss { symbol } => symbol as usize,
-            Outcome::Draw { symbol } => 3 + symbol as usize,
-            Outcome::Win { symbol } => 6 + symbol as usize,
-        }
+pub fn main() -> Result<()> {
+    color_eyre::install()?;
+    let cli = Cli::parse();
+
+    let encoding = match &cli.encoding {
+        Some(path) => std::fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| eyre!("{}: {err}", path.display()))?,
+        None => Encoding::default(),
+    };
+
+    let rules = match &cli.rules {
+        Some(path) => std::fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| eyre!("{}: {err}", path.display()))?,
+        None => ScoringRules::default(),
+    };
+
+    if let (Some(rounds), Some(target)) = (cli.generate, cli.target) {
+        print!(
+            "{}",
+            generate::generate(rounds, target, cli.part, &encoding, &rules)?
+        );
+
+        return Ok(());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    let input = common::input!(day2::EXAMPLE)?;
 
-    #[test]
-    fn test_parse_symbols() {
-        assert_eq!(Symbol::from("A"), Symbol::Rock);
-        assert_eq!(Symbol::from("X"), Symbol::Rock);
-        assert_eq!(Symbol::from("B"), Symbol::Paper);
-        assert_eq!(Symbol::from("Y"), Symbol::Paper);
-        assert_eq!(Symbol::from("C"), Symbol::Scissors);
-        assert_eq!(Symbol::from("Z"), Symbol::Scissors);
+    if let Some(format) = cli.explain {
+        let rows = explain::explain_both(&input, &encoding, &rules)?;
+        print!("{}", explain::render(&rows, format));
 
-        assert_eq!(Symbol::Rock as usize, 1);
-        assert_eq!(Symbol::Paper as usize, 2);
-        assert_eq!(Symbol::Scissors as usize, 3);
+        return Ok(());
     }
 
-    #[test]
-    fn test_outcomes() {
-        let games = [
-            (
-                ("A", "X"),
-                Outcome::Loss {
-                    symbol: Symbol::Scissors,
-                },
-            ),
-            (
-                ("A", "Y"),
-                Outcome::Draw {
-                    symbol: Symbol::Rock,
-                },
-            ),
-            (
-                ("A", "Z"),
-                Outcome::Win {
-                    symbol: Symbol::Paper,
-                },
-            ),
-            (
-                ("B", "X"),
-                Outcome::Loss {
-                    symbol: Symbol::Rock,
-                },
-            ),
-            (
-                ("B", "Y"),
-                Outcome::Draw {
-                    symbol: Symbol::Paper,
-                },
-            ),
-            (
-                ("B", "Z"),
-                Outcome::Win {
-                    symbol: Symbol::Scissors,
-                },
-            ),
-            (
-                ("C", "X"),
-                Outcome::Loss {
-                    symbol: Symbol::Paper,
-                },
-            ),
-            (
-                ("C", "Y"),
-                Outcome::Draw {
-                    symbol: Symbol::Scissors,
-                },
-            ),
-            (
-                ("C", "Z"),
-                Outcome::Win {
-                    symbol: Symbol::Rock,
-                },
-            ),
-        ];
+    if cli.best {
+        let (_, totals) = best::compare(&input, &encoding, &rules)?;
+        print!("{totals}");
 
-        for game in games {
-            assert_eq!(Outcome::from(game.0), game.1);
-        }
+        return Ok(());
     }
 
-    #[test]
-    fn test_outcome_value() {
-        let outcomes = [
-            (
-                Outcome::Draw {
-                    symbol: Symbol::Scissors,
-                },
-                3 + 3,
-            ),
-            (
-                Outcome::Loss {
-                    symbol: Symbol::Rock,
-                },
-                1,
-            ),
-            (
-                Outcome::Win {
-                    symbol: Symbol::Paper,
-                },
-                6 + 2,
-            ),
-        ];
+    if let Some(order) = cli.predict {
+        print!("{}", predict::evaluate(&input, &encoding, &rules, order)?);
 
-        for outcome in outcomes {
-            assert_eq!(<usize>::from(outcome.0), outcome.1);
-        }
+        return Ok(());
     }
 
-    mod p1 {
-        use crate::p1;
+    if let Some(rounds) = cli.tournament {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(tournament::Constant(Symbol::Rock)),
+            Box::new(tournament::Random::new(2022)),
+            Box::new(tournament::Cycle(Symbol::ALL.to_vec())),
+            Box::new(tournament::FrequencyCounter),
+            Box::new(Markov::new(2, rules)),
+            Box::new(tournament::ReplayGuide::new(&input, &encoding)?),
+        ];
 
-        #[test]
-        fn test_example() {
-            let input = indoc::indoc! {"
-                A Y
-                B X
-                C Z
-            "};
+        let table = tournament::round_robin(&mut strategies, rounds, &rules)
+            .ok_or_else(|| eyre!("the scores of the tournament overflow"))?;
+        print!("{}", tournament::Table(&table));
 
-            assert_eq!(p1(input), 15);
-        }
+        return Ok(());
     }
 
-    mod p2 {
-        use crate::p2;
+    let a1 = report(p1_with(&input, &encoding, cli.bad_rounds, &rules)?);
+    println!("{a1:?}");
 
-        #[test]
-        fn test_example() {
-            let input = indoc::indoc! {"
-                A Y
-                B X
-                C Z
-            "};
+    let a2 = report(p2_with(&input, &encoding, cli.bad_rounds, &rules)?);
+    println!("{a2:?}");
 
-            assert_eq!(p2(input), 12);
-        }
-    }
+    Ok(())
 }
@@ -0,0 +1,203 @@
+//! Predicting the opponent's next symbol from the ones before it, and playing the counter
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::best::{self, best_response, Totals};
+use crate::encoding::Encoding;
+use crate::guide;
+use crate::scoring::ScoringRules;
+use crate::tournament::{Strategy, Turn};
+use crate::{Outcome, Symbol};
+
+/// Counts which symbol followed each of the last `0..=order` symbols
+#[derive(Debug, Clone, Default)]
+pub struct Predictor {
+    order: usize,
+    /// Indexed by [`Symbol::index`]
+    counts: HashMap<Vec<Symbol>, [usize; 3]>,
+    seen: Vec<Symbol>,
+}
+
+impl Predictor {
+    pub fn new(order: usize) -> Self {
+        Self {
+            order,
+            ..Default::default()
+        }
+    }
+
+    /// The most common follower of the longest context seen before, `None` until anything is seen
+    pub fn predict(&self) -> Option<Symbol> {
+        (0..=self.order.min(self.seen.len()))
+            .rev()
+            .find_map(|k| self.counts.get(&self.seen[self.seen.len() - k..]))
+            .map(|counts| {
+                // Ties go to the first symbol
+                Symbol::ALL
+                    .into_iter()
+                    .rev()
+                    .max_by_key(|symbol| counts[symbol.index()])
+                    .unwrap()
+            })
+    }
+
+    pub fn observe(&mut self, symbol: Symbol) {
+        for k in 0..=self.order.min(self.seen.len()) {
+            let context = self.seen[self.seen.len() - k..].to_vec();
+            self.counts.entry(context).or_default()[symbol.index()] += 1;
+        }
+
+        self.seen.push(symbol);
+    }
+
+    pub fn seen(&self) -> usize {
+        self.seen.len()
+    }
+}
+
+/// Plays the best response under `rules` to a [`Predictor`] learning the opponent's symbols, Rock
+/// when it knows nothing
+pub struct Markov {
+    pub predictor: Predictor,
+    pub rules: ScoringRules,
+}
+
+impl Markov {
+    pub fn new(order: usize, rules: ScoringRules) -> Self {
+        Self {
+            predictor: Predictor::new(order),
+            rules,
+        }
+    }
+}
+
+impl Strategy for Markov {
+    fn name(&self) -> String {
+        format!("markov order {}", self.predictor.order)
+    }
+
+    fn play(&mut self, history: &[Turn]) -> Symbol {
+        for turn in &history[self.predictor.seen()..] {
+            self.predictor.observe(turn.theirs);
+        }
+
+        self.predictor.predict().map_or(Symbol::Rock, |theirs| {
+            best_response(theirs, &self.rules).symbol()
+        })
+    }
+
+    fn reset(&mut self) {
+        self.predictor = Predictor::new(self.predictor.order);
+    }
+}
+
+/// How the predictor did against a guide's opponent column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    pub order: usize,
+    pub rounds: usize,
+    /// Rounds where the opponent's symbol was predicted right
+    pub correct: usize,
+    pub score: usize,
+    /// The guide read both ways, and the best possible score
+    pub guide: Totals,
+}
+
+impl Report {
+    pub fn accuracy(&self) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+
+        self.correct as f64 / self.rounds as f64
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "order {}: {}/{} predicted ({:.1}%)",
+            self.order,
+            self.correct,
+            self.rounds,
+            self.accuracy() * 100.0
+        )?;
+        writeln!(f, "predictor  {}", self.score)?;
+        writeln!(f, "p1         {}", self.guide.p1)?;
+        writeln!(f, "p2         {}", self.guide.p2)?;
+        writeln!(f, "best       {}", self.guide.best)
+    }
+}
+
+/// Plays the predictor through the guide's opponent column, learning one round at a time
+pub fn evaluate(
+    input: &str,
+    encoding: &Encoding,
+    rules: &ScoringRules,
+    order: usize,
+) -> Result<Report, guide::Error> {
+    let (rounds, guide) = best::compare(input, encoding, rules)?;
+    let mut markov = Markov::new(order, *rules);
+    let mut history = Vec::with_capacity(rounds.len());
+    let mut report = Report {
+        order,
+        rounds: rounds.len(),
+        correct: 0,
+        score: 0,
+        guide,
+    };
+
+    for round in rounds {
+        // Catches up on the history first, so predicts from every earlier round
+        let ours = markov.play(&history);
+
+        if markov.predictor.predict() == Some(round.opponent) {
+            report.correct += 1;
+        }
+
+        report.score = rules
+            .score(Outcome::from((round.opponent, ours)))
+            .and_then(|points| report.score.checked_add(points))
+            .ok_or(guide::Error::Overflow { line: round.line })?;
+        history.push(Turn {
+            ours,
+            theirs: round.opponent,
+        });
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predictor() {
+        let mut predictor = Predictor::new(2);
+        assert_eq!(predictor.predict(), None);
+
+        for symbol in [Symbol::Rock, Symbol::Paper, Symbol::Rock, Symbol::Paper] {
+            predictor.observe(symbol);
+        }
+        // Rock followed the only Rock-Paper
+        assert_eq!(predictor.predict(), Some(Symbol::Rock));
+
+        predictor.observe(Symbol::Scissors);
+        // Never seen anything after Paper-Scissors nor Scissors, so it falls back on overall frequency
+        assert_eq!(predictor.predict(), Some(Symbol::Rock));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let input = "A Y\nB Y\nC Y\n".repeat(10);
+        let report = evaluate(&input, &Encoding::default(), &ScoringRules::PUZZLE, 1).unwrap();
+
+        // Learnt the cycle after the first one: a draw, a draw and a loss, then 9 cycles of wins
+        assert_eq!(report.correct, 27);
+        assert_eq!(report.score, 4 + 5 + 2 + 9 * (8 + 9 + 7));
+        assert_eq!(report.guide.p1, 10 * (8 + 5 + 2));
+    }
+}
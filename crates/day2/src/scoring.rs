@@ -0,0 +1,206 @@
+//! Points for each shape and outcome, the puzzle's or a variant tournament's, for
+//! Rock Paper Scissors or any other [`crate::game::Game`]
+//!
+//! Parsed from a small file where every line is optional, missing ones keeping the puzzle's
+//! values:
+//!
+//! ```text
+//! # Loss Draw Win
+//! outcome: 0 3 6
+//! # Rock Paper Scissors
+//! shape: 1 2 3
+//! # The whole round's points are multiplied, by Loss Draw Win
+//! multiplier: 1 1 2
+//! ```
+
+use std::str::FromStr;
+
+use crate::{Goal, Outcome};
+
+/// Points of a game of `N` symbols, Rock Paper Scissors by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringRules<const N: usize = 3> {
+    /// Indexed by [`Goal`], `Lose` first
+    pub outcome: [usize; 3],
+    /// Indexed by the symbol of the [`crate::game::Game`], [`crate::Symbol::index`] for the puzzle
+    pub shape: [usize; N],
+    /// Applied to the whole round's points, indexed by [`Goal`]
+    pub multipliers: Option<[usize; 3]>,
+}
+
+impl ScoringRules {
+    /// Loss 0, Draw 3, Win 6, plus 1 for Rock, 2 for Paper and 3 for Scissors
+    pub const PUZZLE: Self = Self {
+        outcome: [0, 3, 6],
+        shape: [1, 2, 3],
+        multipliers: None,
+    };
+
+    /// Points for what we played, multiplied. `None` if they overflow
+    pub fn shape_points(&self, outcome: Outcome) -> Option<usize> {
+        self.shape[outcome.symbol().index()].checked_mul(self.multiplier(outcome.goal()))
+    }
+
+    /// Points for how the round ended, multiplied. `None` if they overflow
+    pub fn outcome_points(&self, outcome: Outcome) -> Option<usize> {
+        let goal = outcome.goal();
+        self.outcome[goal as usize].checked_mul(self.multiplier(goal))
+    }
+
+    /// `None` if the round's points overflow
+    pub fn score(&self, outcome: Outcome) -> Option<usize> {
+        self.points(outcome.symbol().index(), outcome.goal())
+    }
+}
+
+impl<const N: usize> ScoringRules<N> {
+    fn multiplier(&self, goal: Goal) -> usize {
+        self.multipliers
+            .map_or(1, |multipliers| multipliers[goal as usize])
+    }
+
+    /// Points for playing `symbol` and ending the round with `goal`. `None` if they overflow
+    pub fn points(&self, symbol: usize, goal: Goal) -> Option<usize> {
+        let multiplier = self.multiplier(goal);
+
+        self.shape[symbol]
+            .checked_mul(multiplier)?
+            .checked_add(self.outcome[goal as usize].checked_mul(multiplier)?)
+    }
+}
+
+/// The puzzle's points for any number of symbols: Loss 0, Draw 3, Win 6, plus the symbol's
+/// position starting at 1
+impl<const N: usize> Default for ScoringRules<N> {
+    fn default() -> Self {
+        Self {
+            outcome: [0, 3, 6],
+            shape: std::array::from_fn(|symbol| symbol + 1),
+            multipliers: None,
+        }
+    }
+}
+
+/// `shape` lists the points of all `N` symbols, missing keys keep their [`Default`] points
+impl<const N: usize> FromStr for ScoringRules<N> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| format!("line {}: {message}", i + 1);
+
+            let (key, values) = line
+                .split_once(':')
+                .ok_or_else(|| error(format!("expected `key: points`, got {line:?}")))?;
+            let values = values
+                .split_whitespace()
+                .map(|value| {
+                    value
+                        .parse()
+                        .map_err(|_| error(format!("{value:?} is not a number of points")))
+                })
+                .collect::<Result<Vec<usize>, _>>()?;
+            let count = |expected: usize| {
+                error(format!("expected {expected} values, got {}", values.len()))
+            };
+
+            match key.trim() {
+                "outcome" => rules.outcome = values.as_slice().try_into().map_err(|_| count(3))?,
+                "shape" => rules.shape = values.as_slice().try_into().map_err(|_| count(N))?,
+                "multiplier" => {
+                    rules.multipliers = Some(values.as_slice().try_into().map_err(|_| count(3))?)
+                }
+                key => {
+                    return Err(error(format!(
+                        "unknown key {key:?}, expected outcome, shape or multiplier"
+                    )))
+                }
+            }
+        }
+
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Symbol;
+
+    #[test]
+    fn test_puzzle() {
+        for symbol in Symbol::ALL {
+            for goal in [Goal::Lose, Goal::Draw, Goal::Win] {
+                let outcome = Outcome::new(goal, symbol);
+                let expected = symbol as usize
+                    + match goal {
+                        Goal::Lose => 0,
+                        Goal::Draw => 3,
+                        Goal::Win => 6,
+                    };
+
+                assert_eq!(ScoringRules::default().score(outcome), Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let rules: ScoringRules = indoc::indoc! {"
+            # Wins count double
+            outcome: 0 1 2
+            multiplier: 1 1 2
+        "}
+        .parse()
+        .unwrap();
+
+        let win = Outcome::Win {
+            symbol: Symbol::Scissors,
+        };
+
+        assert_eq!(rules.shape, [1, 2, 3]);
+        assert_eq!(
+            (rules.shape_points(win), rules.outcome_points(win)),
+            (Some(6), Some(4))
+        );
+        assert_eq!(
+            rules.score(Outcome::Draw {
+                symbol: Symbol::Rock
+            }),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let rules: ScoringRules = format!("shape: 1 2 {}\nmultiplier: 1 1 2\n", usize::MAX)
+            .parse()
+            .unwrap();
+        let scissors = |goal| Outcome::new(goal, Symbol::Scissors);
+
+        assert_eq!(rules.shape_points(scissors(Goal::Win)), None);
+        assert_eq!(rules.score(scissors(Goal::Draw)), None);
+        assert_eq!(rules.shape_points(scissors(Goal::Draw)), Some(usize::MAX));
+        assert_eq!(rules.outcome_points(scissors(Goal::Win)), Some(12));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "shape: 1 2\n".parse::<ScoringRules>(),
+            Err("line 1: expected 3 values, got 2".to_string())
+        );
+        assert_eq!(
+            "\noutcome: 0 three 6\n".parse::<ScoringRules>(),
+            Err("line 2: \"three\" is not a number of points".to_string())
+        );
+        assert!("bonus: 1 2 3\n".parse::<ScoringRules>().is_err());
+    }
+}
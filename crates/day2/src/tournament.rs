@@ -0,0 +1,372 @@
+//! Strategies playing each other over many rounds, scored by any [`ScoringRules`]
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::encoding::Encoding;
+use crate::guide;
+use crate::scoring::ScoringRules;
+use crate::{Goal, Outcome, Symbol};
+
+/// One round of a match, from the point of view of whoever is looking at it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    pub ours: Symbol,
+    pub theirs: Symbol,
+}
+
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// Picks the next symbol knowing every previous round of the match
+    fn play(&mut self, history: &[Turn]) -> Symbol;
+
+    /// Forgets anything learnt, before a new match
+    fn reset(&mut self) {}
+}
+
+/// Always the same symbol
+pub struct Constant(pub Symbol);
+
+impl Strategy for Constant {
+    fn name(&self) -> String {
+        format!("constant {:?}", self.0)
+    }
+
+    fn play(&mut self, _history: &[Turn]) -> Symbol {
+        self.0
+    }
+}
+
+/// Uniformly random, the same sequence for the same seed
+pub struct Random {
+    seed: u64,
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random {}", self.seed)
+    }
+
+    fn play(&mut self, _history: &[Turn]) -> Symbol {
+        // SplitMix64
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+
+        Symbol::from_index((z % 3) as usize)
+    }
+
+    fn reset(&mut self) {
+        self.state = self.seed;
+    }
+}
+
+/// The given symbols in order, over and over. There must be at least one
+pub struct Cycle(pub Vec<Symbol>);
+
+impl Strategy for Cycle {
+    fn name(&self) -> String {
+        format!(
+            "cycle {}",
+            self.0.iter().map(|s| format!("{s:?}")).join("-")
+        )
+    }
+
+    fn play(&mut self, history: &[Turn]) -> Symbol {
+        self.0[history.len() % self.0.len()]
+    }
+}
+
+/// Beats whatever the opponent played the most so far
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".into()
+    }
+
+    fn play(&mut self, history: &[Turn]) -> Symbol {
+        let counts = history.iter().counts_by(|turn| turn.theirs);
+        let favourite = Symbol::ALL
+            .into_iter()
+            .max_by_key(|symbol| counts.get(symbol).copied().unwrap_or_default())
+            .unwrap();
+
+        Outcome::from((favourite, Goal::Win)).symbol()
+    }
+}
+
+/// Our column of a guide read the part 1 way, from the start again once it runs out
+pub struct ReplayGuide(pub Vec<Symbol>);
+
+impl ReplayGuide {
+    /// Fails on any round that can't be read, and plays Rock for an empty guide
+    pub fn new(input: &str, encoding: &Encoding) -> Result<Self, guide::Error> {
+        let mut symbols = guide::rounds(input)
+            .map(|round| round?.response(encoding))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if symbols.is_empty() {
+            symbols.push(Symbol::Rock);
+        }
+
+        Ok(Self(symbols))
+    }
+}
+
+impl Strategy for ReplayGuide {
+    fn name(&self) -> String {
+        "replay guide".into()
+    }
+
+    fn play(&mut self, history: &[Turn]) -> Symbol {
+        self.0[history.len() % self.0.len()]
+    }
+}
+
+/// One line of the league table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    /// Sum of the score of every round played
+    pub score: usize,
+}
+
+impl Standing {
+    /// 3 for a won match, 1 for a draw
+    pub fn points(&self) -> usize {
+        3 * self.won + self.drawn
+    }
+}
+
+/// Every strategy plays every other one for `rounds` rounds. The table is sorted by points, then score
+///
+/// `None` if a score overflows.
+pub fn round_robin(
+    strategies: &mut [Box<dyn Strategy>],
+    rounds: usize,
+    rules: &ScoringRules,
+) -> Option<Vec<Standing>> {
+    let mut table = strategies
+        .iter()
+        .map(|strategy| Standing {
+            name: strategy.name(),
+            won: 0,
+            drawn: 0,
+            lost: 0,
+            score: 0,
+        })
+        .collect_vec();
+
+    for (a, b) in (0..strategies.len()).tuple_combinations() {
+        let (left, right) = strategies.split_at_mut(b);
+        let (score_a, score_b) = play(left[a].as_mut(), right[0].as_mut(), rounds, rules)?;
+
+        table[a].score = table[a].score.checked_add(score_a)?;
+        table[b].score = table[b].score.checked_add(score_b)?;
+
+        match score_a.cmp(&score_b) {
+            std::cmp::Ordering::Greater => (table[a].won += 1, table[b].lost += 1),
+            std::cmp::Ordering::Less => (table[a].lost += 1, table[b].won += 1),
+            std::cmp::Ordering::Equal => (table[a].drawn += 1, table[b].drawn += 1),
+        };
+    }
+
+    table.sort_by_key(|standing| {
+        (
+            std::cmp::Reverse(standing.points()),
+            std::cmp::Reverse(standing.score),
+        )
+    });
+
+    Some(table)
+}
+
+/// One match, the score of each side. `None` if one overflows
+pub fn play(
+    a: &mut dyn Strategy,
+    b: &mut dyn Strategy,
+    rounds: usize,
+    rules: &ScoringRules,
+) -> Option<(usize, usize)> {
+    a.reset();
+    b.reset();
+
+    let mut history_a = Vec::with_capacity(rounds);
+    let mut history_b = Vec::with_capacity(rounds);
+    let (mut score_a, mut score_b) = (0usize, 0usize);
+
+    for _ in 0..rounds {
+        let symbol_a = a.play(&history_a);
+        let symbol_b = b.play(&history_b);
+
+        score_a = rules
+            .score(Outcome::from((symbol_b, symbol_a)))
+            .and_then(|points| score_a.checked_add(points))?;
+        score_b = rules
+            .score(Outcome::from((symbol_a, symbol_b)))
+            .and_then(|points| score_b.checked_add(points))?;
+
+        history_a.push(Turn {
+            ours: symbol_a,
+            theirs: symbol_b,
+        });
+        history_b.push(Turn {
+            ours: symbol_b,
+            theirs: symbol_a,
+        });
+    }
+
+    Some((score_a, score_b))
+}
+
+/// The league table, one strategy per line
+pub struct Table<'a>(pub &'a [Standing]);
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|standing| standing.name.len())
+            .chain(["strategy".len()])
+            .max()
+            .unwrap();
+
+        writeln!(
+            f,
+            "{:<width$}  {:>3}  {:>3}  {:>3}  {:>6}  {:>8}",
+            "strategy", "W", "D", "L", "points", "score"
+        )?;
+
+        for standing in self.0 {
+            writeln!(
+                f,
+                "{:<width$}  {:>3}  {:>3}  {:>3}  {:>6}  {:>8}",
+                standing.name,
+                standing.won,
+                standing.drawn,
+                standing.lost,
+                standing.points(),
+                standing.score
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategies() {
+        let history = [
+            Turn {
+                ours: Symbol::Rock,
+                theirs: Symbol::Paper,
+            },
+            Turn {
+                ours: Symbol::Rock,
+                theirs: Symbol::Paper,
+            },
+            Turn {
+                ours: Symbol::Rock,
+                theirs: Symbol::Scissors,
+            },
+        ];
+
+        assert_eq!(FrequencyCounter.play(&history), Symbol::Scissors);
+        assert_eq!(
+            Cycle(vec![Symbol::Rock, Symbol::Paper]).play(&history),
+            Symbol::Paper
+        );
+
+        let mut random = Random::new(7);
+        let first = (0..10).map(|_| random.play(&[])).collect_vec();
+        random.reset();
+        let again = (0..10).map(|_| random.play(&[])).collect_vec();
+
+        assert_eq!(first, again);
+
+        let mut guide = ReplayGuide::new(crate::EXAMPLE, &Encoding::default()).unwrap();
+        assert_eq!(guide.play(&history), Symbol::Paper);
+        assert!(Symbol::ALL.iter().all(|symbol| first.contains(symbol)));
+    }
+
+    #[test]
+    fn test_play() {
+        // Paper beats Rock every round: 8 points against 1
+        let scores = play(
+            &mut Constant(Symbol::Paper),
+            &mut Constant(Symbol::Rock),
+            10,
+            &ScoringRules::PUZZLE,
+        );
+        assert_eq!(scores, Some((80, 10)));
+
+        // Only outcomes count
+        let rules = ScoringRules {
+            shape: [0, 0, 0],
+            ..ScoringRules::PUZZLE
+        };
+        let scores = play(
+            &mut Constant(Symbol::Paper),
+            &mut Constant(Symbol::Rock),
+            10,
+            &rules,
+        );
+        assert_eq!(scores, Some((60, 0)));
+
+        let rules = ScoringRules {
+            outcome: [0, 3, usize::MAX],
+            ..ScoringRules::PUZZLE
+        };
+        let scores = play(
+            &mut Constant(Symbol::Paper),
+            &mut Constant(Symbol::Rock),
+            10,
+            &rules,
+        );
+        assert_eq!(scores, None);
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Constant(Symbol::Rock)),
+            Box::new(FrequencyCounter),
+            Box::new(Cycle(vec![Symbol::Rock, Symbol::Paper, Symbol::Scissors])),
+        ];
+
+        let table = round_robin(&mut strategies, 300, &ScoringRules::PUZZLE).unwrap();
+
+        assert_eq!(table[0].name, "frequency counter");
+        assert_eq!(
+            table
+                .iter()
+                .map(|s| s.won + s.drawn + s.lost)
+                .sum::<usize>(),
+            6
+        );
+        assert_eq!(
+            table.iter().map(Standing::points).max(),
+            Some(table[0].points())
+        );
+    }
+}
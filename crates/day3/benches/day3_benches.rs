@@ -2,7 +2,7 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use day3::{fast, slow};
 
 fn day3_benches(c: &mut Criterion) {
-    let input = std::fs::read_to_string("src/input.txt").unwrap();
+    let input = common::input!(day3::EXAMPLE).unwrap();
 
     let mut group = c.benchmark_group("day3.p1");
     group.bench_function("slow", |b| {
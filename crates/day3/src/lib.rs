@@ -1,2 +1,15 @@
 pub mod fast;
 pub mod slow;
+
+/// Used when the real input isn't available
+pub const EXAMPLE: &str = indoc::indoc! {"
+    vJrwpWtwJgWrhcsFMMfFFhFp
+    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+    PmmdzqPrVvPwwTWBwg
+    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+    ttgJtRGJQctTZtZT
+    CrZsJsPPZsGzwwsLwLmpwMDw
+"};
+
+/// Part of the key of cached answers, changes with the code of the crate
+pub const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("AOC_SOURCE_HASH"));
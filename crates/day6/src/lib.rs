@@ -0,0 +1,62 @@
+/// Used when the real input isn't available
+pub const EXAMPLE: &str = indoc::indoc! {"
+    mjqjpqmgbljsphdztnvjfqwrcgsmlb
+"};
+
+/// Part of the key of cached answers, changes with the code of the crate
+pub const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("AOC_SOURCE_HASH"));
+
+/// Find position of 4 consecutive unique chars
+pub fn p1(input: &str) -> usize {
+    solver(input, 4)
+}
+
+/// Find position of 14 consecutive unique chars
+pub fn p2(input: &str) -> usize {
+    solver(input, 14)
+}
+
+fn solver(input: &str, window_size: usize) -> usize {
+    // Solution with HashSet
+    //
+    // use std::collections::HashSet;
+    //
+    // input
+    //     .as_bytes()
+    //     .windows(window_size)
+    //     .position(|slice| slice.iter().collect::<HashSet<_>>().len() == window_size)
+    //     .map(|pos| pos + window_size)
+    //     .unwrap()
+
+    input
+        .as_bytes()
+        .windows(window_size)
+        .position(|slice| {
+            let mut state = 0u32;
+
+            for letter in slice {
+                state |= 1 << (letter % 32);
+            }
+
+            window_size == state.count_ones() as usize
+        })
+        .map(|position| position + window_size)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod p1 {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(p1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
+            assert_eq!(p1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
+            assert_eq!(p1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
+            assert_eq!(p1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+        }
+    }
+}
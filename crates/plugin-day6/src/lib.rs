@@ -0,0 +1,79 @@
+//! Alternative day6 solution, loaded by `aoc` as a plugin
+//!
+//! ```sh
+//! cargo build --release -p plugin-day6
+//! mkdir -p .aoc/plugins && cp target/release/libplugin_day6.so .aoc/plugins
+//! cargo run -p aoc -- compare 6
+//! ```
+
+common::export_plugin! {
+    (6, 1, "sliding", p1),
+    (6, 2, "sliding", p2),
+}
+
+fn p1(input: &str) -> usize {
+    solver(input, 4)
+}
+
+fn p2(input: &str) -> usize {
+    solver(input, 14)
+}
+
+/// Instead of checking every window from scratch, count the letters entering and leaving it
+fn solver(input: &str, window_size: usize) -> usize {
+    let input = input.as_bytes();
+    let mut counts = [0u8; 256];
+    let mut unique = 0;
+
+    for (i, &letter) in input.iter().enumerate() {
+        counts[letter as usize] += 1;
+        if counts[letter as usize] == 1 {
+            unique += 1;
+        }
+
+        if i >= window_size {
+            let leaving = input[i - window_size] as usize;
+
+            counts[leaving] -= 1;
+            if counts[leaving] == 0 {
+                unique -= 1;
+            }
+        }
+
+        if unique == window_size {
+            return i + 1;
+        }
+    }
+
+    panic!("no marker of {window_size} unique letters")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod p1 {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(p1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
+            assert_eq!(p1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
+            assert_eq!(p1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
+            assert_eq!(p1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+        }
+    }
+
+    mod p2 {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(p2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
+            assert_eq!(p2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
+            assert_eq!(p2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
+            assert_eq!(p2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
+            assert_eq!(p2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+        }
+    }
+}